@@ -2,7 +2,7 @@ use std::hint::black_box;
 
 use criterion::{Criterion, criterion_group, criterion_main};
 use evaluator::{Evaluator, Impartial};
-use taking_game::builder::get_known_games;
+use taking_game::builder::{Builder, get_known_games};
 
 fn bench_nimber_computation(c: &mut Criterion) {
     let known_games = get_known_games();
@@ -43,10 +43,19 @@ fn bench_move_generation(c: &mut Criterion) {
     });
 }
 
+fn bench_kayles_move_generation(c: &mut Criterion) {
+    let kayles = Builder::kayles(12).build_one().unwrap();
+
+    c.bench_function("kayles move generation", |b| {
+        b.iter(|| black_box(kayles.get_split_moves()))
+    });
+}
+
 criterion_group!(
     benches,
     bench_nimber_computation,
     bench_symmetry,
-    bench_move_generation
+    bench_move_generation,
+    bench_kayles_move_generation
 );
 criterion_main!(benches);
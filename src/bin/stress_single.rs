@@ -0,0 +1,55 @@
+use std::{
+    env,
+    fs::File,
+    io::BufReader,
+    time::{Duration, Instant},
+};
+
+use evaluator::Evaluator;
+use taking_game::builder::Builder;
+
+/// Repeatedly builds a single game and computes its nimber for a fixed duration.
+///
+/// Usage: `stress_single <game> [seconds]`, where `<game>` is either the name of
+/// a position in `Builder::book()` or a path to a file in the format read by
+/// `Builder::from_dimacs_like`.
+fn main() {
+    let mut args = env::args().skip(1);
+    let Some(game) = args.next() else {
+        eprintln!("usage: stress_single <game> [seconds]");
+        eprintln!("available games:");
+        for (name, _) in Builder::book() {
+            eprintln!("  {name}");
+        }
+        return;
+    };
+    let seconds: u64 = args
+        .next()
+        .map_or(10, |s| s.parse().expect("invalid duration"));
+
+    // Parse once, so that only building and evaluating are timed.
+    let builder = match Builder::book().into_iter().find(|(name, _)| *name == game) {
+        Some((_, builder)) => builder,
+        None => {
+            let file = File::open(&game).expect("game is neither a book entry nor a readable file");
+            Builder::from_dimacs_like(BufReader::new(file)).expect("invalid hypergraph file")
+        }
+    };
+
+    let duration = Duration::from_secs(seconds);
+    let start = Instant::now();
+    let mut iterations = 0u64;
+    let mut nimber = None;
+    while start.elapsed() < duration {
+        let parts = builder.clone().build();
+        let evaluator = Evaluator::new();
+        nimber = evaluator.get_nimber_by_parts(&parts);
+        iterations += 1;
+    }
+    let elapsed = start.elapsed().as_secs_f64();
+    println!("nimber: {nimber:?}");
+    println!(
+        "{iterations} iterations in {elapsed:.2}s ({:.2} ops/sec)",
+        iterations as f64 / elapsed
+    );
+}
@@ -0,0 +1,62 @@
+use super::Builder;
+
+impl Builder {
+    /// Returns a collection of named, documented positions for tests and examples.
+    pub fn book() -> Vec<(String, Builder)> {
+        vec![
+            // nimber 5
+            ("heap of 5".to_string(), Builder::heap(5)),
+            // nimber 2
+            ("kayles of 7".to_string(), Builder::kayles(7)),
+            // nimber 7
+            ("kayles of 15".to_string(), Builder::kayles(15)),
+            // nimber 0, has a symmetry
+            ("square 2x2".to_string(), Builder::rect(2, 2)),
+            // nimber 0, no symmetry
+            ("square 3x3".to_string(), Builder::rect(3, 3)),
+            // nimber 0, has a symmetry
+            ("square 4x4".to_string(), Builder::rect(4, 4)),
+            ("rectangle 3x4".to_string(), Builder::rect(3, 4)),
+            ("triangle of side 4".to_string(), Builder::triangle(4)),
+            // nimber 0, has a symmetry
+            ("cube 2x2x2".to_string(), Builder::hyper_cube(3, 2)),
+            (
+                "tetrahedron of dimension 4".to_string(),
+                Builder::hyper_tetrahedron(4),
+            ),
+            (
+                "random tree of 10 nodes".to_string(),
+                Builder::random_tree(10, 0),
+            ),
+            // 1 ^ 2 ^ 3 = 0
+            (
+                "P-position: heaps of 1, 2 and 3".to_string(),
+                Builder::disjoint_heaps(&[1, 2, 3]),
+            ),
+            // 3 ^ 5 ^ 7 = 1, the winning move takes one token from any heap
+            (
+                "puzzle: heaps of 3, 5 and 7".to_string(),
+                Builder::disjoint_heaps(&[3, 5, 7]),
+            ),
+        ]
+    }
+
+    fn disjoint_heaps(sizes: &[usize]) -> Builder {
+        Builder::disjoint_sum_of(sizes.iter().map(|&size| Builder::heap(size)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_book_builds() {
+        let book = Builder::book();
+        assert!(!book.is_empty());
+        for (name, builder) in book {
+            assert!(!name.is_empty());
+            assert!(!builder.build().is_empty(), "{name} should build");
+        }
+    }
+}
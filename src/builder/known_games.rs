@@ -66,9 +66,13 @@ pub fn get_known_games() -> Vec<KnownGame> {
         KnownGame::from_builder(Builder::heap(101))
             .nimber(101)
             .not_symmetric(),
-        KnownGame::from_builder(Builder::heap(16).sum(Builder::heap(8).sum(Builder::heap(7))))
-            .nimber(31)
-            .not_symmetric(),
+        KnownGame::from_builder(Builder::disjoint_sum_of(vec![
+            Builder::heap(16),
+            Builder::heap(8),
+            Builder::heap(7),
+        ]))
+        .nimber(31)
+        .not_symmetric(),
         KnownGame::from_builder(Builder::rect(2, 2))
             .nimber(0)
             .symmetric(),
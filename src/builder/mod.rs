@@ -1,7 +1,12 @@
+mod book;
 mod known_games;
+mod text_format;
+use itertools::Itertools;
 pub use known_games::get_known_games;
-use rand::{Rng, rng};
+use rand::{Rng, SeedableRng, rng, rngs::StdRng};
+use std::io::{self, BufRead};
 use std::vec;
+pub use text_format::{ParseError, ParseErrorKind};
 
 use crate::taking_game::TakingGame;
 
@@ -9,9 +14,11 @@ use crate::taking_game::TakingGame;
 ///
 /// Provides utilities for buid_one()ing graphs from hyperedges, performing transformations
 /// like extrusion and connection, and generating standard structures (e.g., grids, cubes).
-#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Builder {
     hyperedges: Vec<Vec<usize>>,
+    /// Original labels of the nodes `0..labels.len()`, empty if nodes are their own labels.
+    labels: Vec<usize>,
 }
 impl Builder {
     pub fn get_nodes(&self) -> Vec<usize> {
@@ -23,17 +30,197 @@ impl Builder {
     pub fn get_max_node(&self) -> usize {
         self.get_nodes().pop().unwrap_or(0)
     }
+    /// Builds the game, returning its connected components.
+    ///
+    /// Components are sorted by their canonical form, ties broken by node labels,
+    /// so the output order is deterministic.
     pub fn build(self) -> Vec<TakingGame> {
-        TakingGame::from_hyperesges(self.hyperedges)
+        if self.labels.is_empty() {
+            TakingGame::from_hyperesges(self.hyperedges)
+        } else {
+            let labels = self.extended_labels();
+            TakingGame::from_hyperedges_with_labels(self.hyperedges, labels)
+        }
     }
+    /// Builds the game and returns a single component.
+    ///
+    /// For connected input this is the whole game. For disconnected input it is
+    /// the part with the most nodes, ties broken by taking the last such part in
+    /// the order of [`Builder::build`]. Returns `None` if the game has no nodes.
     pub fn build_one(self) -> Option<TakingGame> {
-        let mut games = self.build();
-        games.sort_by_key(|g| g.nr_nodes());
-        games.pop()
+        self.build().into_iter().max_by_key(|g| g.nr_nodes())
+    }
+    /// Returns the label of every node, labelling nodes added after
+    /// [`Builder::from_labeled_hyperedges`] with fresh labels above all existing ones.
+    fn extended_labels(&self) -> Vec<usize> {
+        let node_count = self.get_nodes().last().map_or(0, |max| max + 1);
+        let mut labels = self.labels.clone();
+        let fresh = labels.iter().max().map_or(0, |max| max + 1);
+        labels.extend(
+            (labels.len()..node_count.max(labels.len())).map(|i| fresh + i - self.labels.len()),
+        );
+        labels
     }
     /// Creates a `Builder` from a given list of sets of nodes (hyperedges).
     pub fn from_hyperedges(hyperedges: Vec<Vec<usize>>) -> Builder {
-        Builder { hyperedges }
+        Builder {
+            hyperedges,
+            labels: vec![],
+        }
+    }
+    /// Creates a `Builder` from sets of arbitrary, possibly sparse node labels.
+    ///
+    /// The labels are compressed to `0..n` internally, which is what the other
+    /// methods of `Builder` operate on, and restored as the [`TakingGame::nodes`]
+    /// of the built games. Nodes added afterwards, e.g. by [`Builder::sum`],
+    /// get fresh labels above all given ones.
+    pub fn from_labeled_hyperedges(edges: Vec<Vec<usize>>) -> Builder {
+        let mut labels: Vec<usize> = edges.iter().flatten().copied().collect();
+        labels.sort();
+        labels.dedup();
+        let hyperedges = edges
+            .iter()
+            .map(|e| {
+                e.iter()
+                    .map(|n| labels.binary_search(n).expect("every label was collected"))
+                    .collect()
+            })
+            .collect();
+        Builder { hyperedges, labels }
+    }
+    /// Creates a `Builder` from the hyperedges of a built game, using its original node labels.
+    pub fn from_game(game: &TakingGame) -> Builder {
+        Builder::from_labeled_hyperedges(game.labeled_hyperedges())
+    }
+    /// Creates a `Builder` for a simple graph on `n` nodes, one 2-node set per edge.
+    ///
+    /// Isolated nodes become single-node sets.
+    ///
+    /// Panics if an endpoint is not less than `n`.
+    pub fn from_edge_list(n: usize, edges: &[(usize, usize)]) -> Builder {
+        let mut has_edge = vec![false; n];
+        let mut hyperedges = Vec::with_capacity(edges.len());
+        for &(a, b) in edges {
+            assert!(
+                a < n && b < n,
+                "edge ({a}, {b}) has an endpoint out of range for {n} nodes"
+            );
+            has_edge[a] = true;
+            has_edge[b] = true;
+            hyperedges.push(vec![a, b]);
+        }
+        for (node, _) in has_edge.iter().enumerate().filter(|(_, e)| !**e) {
+            hyperedges.push(vec![node]);
+        }
+        Builder::from_hyperedges(hyperedges)
+    }
+    /// Creates a `Builder` from the adjacency matrix of a simple graph, see [`Builder::from_edge_list`].
+    ///
+    /// Panics if the matrix is not square or not symmetric.
+    pub fn from_adjacency_matrix(matrix: &[Vec<bool>]) -> Builder {
+        let n = matrix.len();
+        for (i, row) in matrix.iter().enumerate() {
+            assert_eq!(
+                row.len(),
+                n,
+                "adjacency matrix is not square: row {i} has {} entries, expected {n}",
+                row.len()
+            );
+        }
+        let mut edges = vec![];
+        for (j, row) in matrix.iter().enumerate() {
+            for (i, &adjacent) in row.iter().enumerate().take(j) {
+                assert_eq!(
+                    adjacent, matrix[i][j],
+                    "adjacency matrix is not symmetric at ({i}, {j})"
+                );
+                if adjacent {
+                    edges.push((i, j));
+                }
+            }
+        }
+        Builder::from_edge_list(n, &edges)
+    }
+    /// Reads a `Builder` from a simple line-based hypergraph format.
+    ///
+    /// The first line holds the node count; every following non-blank line
+    /// lists the node indices of one hyperedge, as in [`Builder::from_str_format`].
+    /// Malformed input yields an [`io::ErrorKind::InvalidData`] error wrapping a
+    /// [`ParseError`].
+    pub fn from_dimacs_like(reader: impl BufRead) -> io::Result<Builder> {
+        let invalid =
+            |line, kind| io::Error::new(io::ErrorKind::InvalidData, ParseError { line, kind });
+        let mut lines = reader.lines();
+        let header = lines
+            .next()
+            .ok_or_else(|| invalid(1, ParseErrorKind::MissingNodeCount))??;
+        let node_count: usize = header
+            .trim()
+            .parse()
+            .map_err(|_| invalid(1, ParseErrorKind::InvalidNodeCount(header.clone())))?;
+        let mut hyperedges = vec![];
+        for (i, line) in lines.enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let edge = text_format::parse_edge_line(&line, i + 2)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            if let Some(&node) = edge.iter().find(|&&node| node >= node_count) {
+                return Err(invalid(
+                    i + 2,
+                    ParseErrorKind::NodeOutOfRange { node, node_count },
+                ));
+            }
+            hyperedges.push(edge);
+        }
+        Ok(Builder::from_hyperedges(hyperedges))
+    }
+    /// Decodes a simple graph in graph6 format, one 2-node set per edge.
+    ///
+    /// Isolated nodes become single-node sets. Returns `None` if the input is malformed.
+    pub fn from_graph6(s: &str) -> Option<Builder> {
+        let mut bytes = s
+            .trim()
+            .bytes()
+            .map(|b| b.checked_sub(63).filter(|&b| b < 64));
+        let first = bytes.next()??;
+        let n = if first < 63 {
+            first as usize
+        } else {
+            let mut n = 0;
+            for _ in 0..3 {
+                n = (n << 6) | bytes.next()?? as usize;
+            }
+            n
+        };
+        let bits: Vec<bool> = bytes
+            .map(|b| b.map(|b| (0..6).rev().map(move |k| (b >> k) & 1 == 1)))
+            .collect::<Option<Vec<_>>>()?
+            .into_iter()
+            .flatten()
+            .collect();
+        let pair_count = n * n.saturating_sub(1) / 2;
+        if bits.len() != pair_count.div_ceil(6) * 6 {
+            return None;
+        }
+        let mut hyperedges = vec![];
+        let mut has_edge = vec![false; n];
+        let mut k = 0;
+        for j in 1..n {
+            for i in 0..j {
+                if bits[k] {
+                    hyperedges.push(vec![i, j]);
+                    has_edge[i] = true;
+                    has_edge[j] = true;
+                }
+                k += 1;
+            }
+        }
+        for (node, _) in has_edge.iter().enumerate().filter(|(_, e)| !**e) {
+            hyperedges.push(vec![node]);
+        }
+        Some(Builder::from_hyperedges(hyperedges))
     }
     /// Returns a graph with one empty set (no nodes).
     pub fn empty() -> Builder {
@@ -46,6 +233,10 @@ impl Builder {
     pub fn heap(size: usize) -> Builder {
         Builder::from_hyperedges(vec![(0..size).collect()])
     }
+    /// Returns the simplest game with nimber `k`, a heap of size `k`.
+    pub fn target_nimber(k: usize) -> Builder {
+        Builder::heap(k)
+    }
     /// Constructs a Kayles game of the given size.
     ///
     /// Each set connects two adjacent nodes. Returns `empty()` if size == 0,
@@ -63,6 +254,33 @@ impl Builder {
         }
         Builder::from_hyperedges(hyperedges)
     }
+    /// Constructs the path graph on `n` nodes, the same game as `kayles(n)`.
+    pub fn path(n: usize) -> Builder {
+        Builder::kayles(n)
+    }
+    /// Constructs the star graph on `n` nodes, where the center node `0`
+    /// shares a 2-node set with each of the nodes `1..n`.
+    ///
+    /// Returns `empty()` if n == 0 and `unit()` if n == 1.
+    pub fn star(n: usize) -> Builder {
+        match n {
+            0 => Builder::empty(),
+            1 => Builder::unit(),
+            _ => Builder::from_hyperedges((1..n).map(|i| vec![0, i]).collect()),
+        }
+    }
+    /// Constructs a circular Kayles game of the given size.
+    ///
+    /// Like `kayles`, but node `size - 1` also shares a set with node `0`.
+    /// Returns `empty()` if size == 0, `unit()` if size == 1 and `heap(2)` if size == 2.
+    pub fn cycle(size: usize) -> Builder {
+        match size {
+            0 => Builder::empty(),
+            1 => Builder::unit(),
+            2 => Builder::heap(2),
+            _ => Builder::from_hyperedges((0..size).map(|i| vec![i, (i + 1) % size]).collect()),
+        }
+    }
     /// Generates a random hypergraph with the given number of nodes and sets.
     ///
     /// Each node is connected to a random number of sets, within the given bounds.
@@ -80,6 +298,96 @@ impl Builder {
         }
         Builder::from_hyperedges(hyperedges)
     }
+    /// Generates a random tree with the given number of nodes from a seed.
+    ///
+    /// Each new node is attached by a 2-node set to a uniformly chosen existing node,
+    /// so the same seed always produces the same tree.
+    pub fn random_tree(nodes: usize, seed: u64) -> Builder {
+        if nodes == 0 {
+            return Builder::empty();
+        }
+        if nodes == 1 {
+            return Builder::unit();
+        }
+        let mut rng = StdRng::seed_from_u64(seed);
+        let hyperedges = (1..nodes)
+            .map(|node| vec![rng.random_range(..node), node])
+            .collect();
+        Builder::from_hyperedges(hyperedges)
+    }
+
+    /// Constructs the complete graph K_n, where every pair of nodes shares a 2-node set.
+    ///
+    /// Returns `empty()` if n == 0 and `unit()` if n == 1.
+    pub fn complete_graph(n: usize) -> Builder {
+        match n {
+            0 => Builder::empty(),
+            1 => Builder::unit(),
+            _ => Builder::from_hyperedges(
+                (0..n)
+                    .tuple_combinations()
+                    .map(|(i, j)| vec![i, j])
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Constructs the complete bipartite graph K_{m,n}.
+    ///
+    /// Nodes `0..m` form one part and `m..m + n` the other. Every pair of nodes
+    /// in different parts shares a 2-node set, nodes within a part share none.
+    /// If a part is empty, the other consists of isolated single-node sets.
+    pub fn complete_bipartite(m: usize, n: usize) -> Builder {
+        if m + n == 0 {
+            return Builder::empty();
+        }
+        if m == 0 || n == 0 {
+            return Builder::from_hyperedges((0..m + n).map(|i| vec![i]).collect());
+        }
+        Builder::from_hyperedges(
+            (0..m)
+                .cartesian_product(m..m + n)
+                .map(|(i, j)| vec![i, j])
+                .collect(),
+        )
+    }
+
+    /// Constructs the Turán graph T(n, r), the complete `r`-partite graph on `n` nodes.
+    ///
+    /// Node `i` belongs to part `i % r`, so parts differ in size by at most one.
+    /// Every pair of nodes in different parts shares a 2-node set.
+    pub fn turan(n: usize, r: usize) -> Builder {
+        if n == 0 {
+            return Builder::empty();
+        }
+        if r <= 1 || n == 1 {
+            return Builder::from_hyperedges((0..n).map(|i| vec![i]).collect());
+        }
+        let mut hyperedges = vec![];
+        for j in 1..n {
+            for i in 0..j {
+                if i % r != j % r {
+                    hyperedges.push(vec![i, j]);
+                }
+            }
+        }
+        Builder::from_hyperedges(hyperedges)
+    }
+
+    /// Constructs the generalized Petersen graph GP(n, k).
+    ///
+    /// Nodes `0..n` form the outer n-cycle, nodes `n..2n` the inner star polygon
+    /// where `n + i` is adjacent to `n + (i + k) % n`, and each `i` has a spoke to `n + i`.
+    /// GP(5, 2) is the Petersen graph.
+    pub fn generalized_petersen(n: usize, k: usize) -> Builder {
+        let mut hyperedges = vec![];
+        for i in 0..n {
+            hyperedges.push(vec![i, (i + 1) % n]);
+            hyperedges.push(vec![n + i, n + (i + k) % n]);
+            hyperedges.push(vec![i, n + i]);
+        }
+        Builder::from_hyperedges(hyperedges)
+    }
 
     /// Constructs a triangular grid of side length `l` using 3-directional diagonals.
     ///
@@ -107,10 +415,171 @@ impl Builder {
         }
         Builder::from_hyperedges(hyperedges)
     }
+    /// Constructs a hexagonal tiling of `rows` by `cols` cells in brick-wall layout.
+    ///
+    /// Cell `(r, c)` is node `c + r * cols`. Odd rows are shifted half a cell to the
+    /// right, so cell `(r, c)` touches `(r, c ± 1)` and, in the rows above and below,
+    /// `(r ± 1, c - 1)` and `(r ± 1, c)` if `r` is even, or `(r ± 1, c)` and
+    /// `(r ± 1, c + 1)` if `r` is odd. Every pair of touching cells shares a 2-node set.
+    pub fn hexagonal_grid(rows: usize, cols: usize) -> Builder {
+        if rows == 0 || cols == 0 {
+            return Builder::empty();
+        }
+        if rows == 1 && cols == 1 {
+            return Builder::unit();
+        }
+        let index = |r: usize, c: usize| c + r * cols;
+        let mut hyperedges = vec![];
+        for r in 0..rows {
+            for c in 0..cols {
+                if c + 1 < cols {
+                    hyperedges.push(vec![index(r, c), index(r, c + 1)]);
+                }
+                if r + 1 < rows {
+                    let (left, right) = if r % 2 == 0 {
+                        (c.checked_sub(1), Some(c))
+                    } else {
+                        (Some(c), Some(c + 1).filter(|&c| c < cols))
+                    };
+                    for below in [left, right].into_iter().flatten() {
+                        hyperedges.push(vec![index(r, c), index(r + 1, below)]);
+                    }
+                }
+            }
+        }
+        Builder::from_hyperedges(hyperedges)
+    }
     /// Constructs a 2D rectangular grid of size x by y.
     pub fn rect(x: usize, y: usize) -> Builder {
         Self::hyper_cuboid(vec![x, y])
     }
+    /// Constructs an `x` by `y` grid graph wrapping around along the x axis if `wrap_x`,
+    /// otherwise along the y axis.
+    ///
+    /// Unlike `rect`, whose sets are entire rows and columns, every cell shares a
+    /// 2-node set with each orthogonal neighbour, as in Kayles. Cell `(i, j)` is node `i + j * x`.
+    pub fn cylinder(x: usize, y: usize, wrap_x: bool) -> Builder {
+        Self::grid_graph(x, y, wrap_x, !wrap_x)
+    }
+    /// Constructs an `x` by `y` grid graph wrapping around along both axes.
+    ///
+    /// Every row and column is a cycle, so no cell lies on a boundary.
+    /// Cell `(i, j)` is node `i + j * x`.
+    pub fn torus(x: usize, y: usize) -> Builder {
+        Self::grid_graph(x, y, true, true)
+    }
+    /// Constructs a `length` by `width` grid graph glued into a Möbius strip.
+    ///
+    /// Cell `(i, j)` is node `i + j * length`, with `i` running along the strip.
+    /// Like [`Builder::cylinder`] every cell shares a 2-node set with each orthogonal
+    /// neighbour, but the ends are glued with a flip: cell `(length - 1, j)` is
+    /// joined to cell `(0, width - 1 - j)`. As for the cylinder, the gluing is
+    /// skipped if `length` is shorter than 3.
+    pub fn mobius_strip(length: usize, width: usize) -> Builder {
+        let mut strip = Self::grid_graph(length, width, false, false);
+        if length >= 3 && width > 0 {
+            for j in 0..width {
+                strip
+                    .hyperedges
+                    .push(vec![length - 1 + j * length, (width - 1 - j) * length]);
+            }
+        }
+        strip
+    }
+    /// Constructs an `x` by `y` board where every cell shares a 2-node set with each
+    /// of its up to eight neighbours a chess king can reach.
+    ///
+    /// Corner cells have 3 neighbours and interior cells 8. Cell `(i, j)` is node `i + j * x`.
+    pub fn king_grid(x: usize, y: usize) -> Builder {
+        if x == 0 || y == 0 {
+            return Builder::empty();
+        }
+        if x == 1 && y == 1 {
+            return Builder::unit();
+        }
+        let index = |i: usize, j: usize| i + j * x;
+        let mut hyperedges = vec![];
+        for j in 0..y {
+            for i in 0..x {
+                if i + 1 < x {
+                    hyperedges.push(vec![index(i, j), index(i + 1, j)]);
+                }
+                if j + 1 < y {
+                    hyperedges.push(vec![index(i, j), index(i, j + 1)]);
+                    if i + 1 < x {
+                        hyperedges.push(vec![index(i, j), index(i + 1, j + 1)]);
+                    }
+                    if i > 0 {
+                        hyperedges.push(vec![index(i, j), index(i - 1, j + 1)]);
+                    }
+                }
+            }
+        }
+        Builder::from_hyperedges(hyperedges)
+    }
+    /// Constructs an `x` by `y` grid graph of 2-node sets, optionally wrapping around either axis.
+    ///
+    /// Wrapping is skipped along axes shorter than 3, where it would not add new neighbours.
+    fn grid_graph(x: usize, y: usize, wrap_x: bool, wrap_y: bool) -> Builder {
+        if x == 0 || y == 0 {
+            return Builder::empty();
+        }
+        if x == 1 && y == 1 {
+            return Builder::unit();
+        }
+        let index = |i: usize, j: usize| i + j * x;
+        let mut hyperedges = vec![];
+        for j in 0..y {
+            for i in 0..x {
+                if i + 1 < x {
+                    hyperedges.push(vec![index(i, j), index(i + 1, j)]);
+                } else if wrap_x && x >= 3 {
+                    hyperedges.push(vec![index(i, j), index(0, j)]);
+                }
+                if j + 1 < y {
+                    hyperedges.push(vec![index(i, j), index(i, j + 1)]);
+                } else if wrap_y && y >= 3 {
+                    hyperedges.push(vec![index(i, j), index(i, 0)]);
+                }
+            }
+        }
+        Builder::from_hyperedges(hyperedges)
+    }
+    /// Constructs an `x` by `y` board like [`Builder::rect`] with the cells in
+    /// `holes` missing.
+    ///
+    /// As on a physical board, a hole splits its row and column: every maximal run
+    /// of present cells in a row or column becomes one set. Deleting cells may
+    /// therefore disconnect the board. Cell `(i, j)` is node `i + j * x`.
+    ///
+    /// Panics if a hole lies outside the board.
+    pub fn grid_with_holes(x: usize, y: usize, holes: &[(usize, usize)]) -> Builder {
+        for &(i, j) in holes {
+            assert!(
+                i < x && j < y,
+                "hole ({i}, {j}) is outside the {x}x{y} board"
+            );
+        }
+        let present = |i: usize, j: usize| !holes.contains(&(i, j));
+        let mut hyperedges = vec![];
+        let mut push_runs = |cells: Vec<(usize, usize)>| {
+            for run in cells.split(|&(i, j)| !present(i, j)) {
+                if !run.is_empty() {
+                    hyperedges.push(run.iter().map(|&(i, j)| i + j * x).collect());
+                }
+            }
+        };
+        for j in 0..y {
+            push_runs((0..x).map(|i| (i, j)).collect());
+        }
+        for i in 0..x {
+            push_runs((0..y).map(|j| (i, j)).collect());
+        }
+        if hyperedges.is_empty() {
+            return Builder::empty();
+        }
+        Builder::from_hyperedges(hyperedges)
+    }
     /// Constructs a hypercube of dimension `dim` and side length `l` in each dimension.
     ///
     /// Uses `hyper_cuboid` internally.
@@ -190,23 +659,159 @@ impl Builder {
         }
         self
     }
+    /// Returns the disjoint sum of two games.
+    ///
+    /// The nodes of `other` are relabelled above `self.get_max_node()` and its sets
+    /// appended without any connecting sets, so the built result splits back into
+    /// the components of both games. Labels from [`Builder::from_labeled_hyperedges`]
+    /// are kept, those of `other` offset above all labels of `self`.
     pub fn sum(mut self, other: Self) -> Self {
         let shift = self.get_max_node() + 1;
+        if !self.labels.is_empty() || !other.labels.is_empty() {
+            let mut labels = self.extended_labels();
+            let mut offset = labels.iter().max().map_or(0, |max| max + 1);
+            while labels.len() < shift {
+                labels.push(offset);
+                offset += 1;
+            }
+            labels.extend(other.extended_labels().iter().map(|l| l + offset));
+            self.labels = labels;
+        }
         for e in other.hyperedges {
             self.hyperedges.push(e.iter().map(|n| n + shift).collect());
         }
         self
     }
+    /// Returns the disjoint sum of all given builders, see [`Builder::sum`].
+    ///
+    /// Returns `empty()` if no builders are given.
+    pub fn disjoint_sum_of(builders: Vec<Builder>) -> Builder {
+        builders
+            .into_iter()
+            .reduce(Builder::sum)
+            .unwrap_or_else(Builder::empty)
+    }
+    /// Returns `n` disjoint copies of the game, see [`Builder::sum`].
+    ///
+    /// Copy `k` has its nodes shifted by `k * (get_max_node() + 1)`. Labels from
+    /// [`Builder::from_labeled_hyperedges`] are replicated, copy `k` shifting them
+    /// by `k` times one more than the largest label. Returns `empty()` if `n` is zero.
+    pub fn disjoint_copies(self, n: usize) -> Builder {
+        if n == 0 {
+            return Builder::empty();
+        }
+        let shift = self.get_max_node() + 1;
+        let hyperedges = (0..n)
+            .flat_map(|k| {
+                self.hyperedges
+                    .iter()
+                    .map(move |e| e.iter().map(|node| node + k * shift).collect())
+            })
+            .collect();
+        if self.labels.is_empty() {
+            return Builder::from_hyperedges(hyperedges);
+        }
+        let labels = self.extended_labels();
+        let label_shift = labels.iter().max().map_or(0, |max| max + 1);
+        let labels = (0..n)
+            .flat_map(|k| labels.iter().map(move |label| label + k * label_shift))
+            .collect();
+        Builder { hyperedges, labels }
+    }
+    /// Flips `edge_flips` randomly chosen 2-node sets among the existing nodes.
+    ///
+    /// Each flip picks two distinct nodes of `get_nodes()` and removes the set
+    /// joining them if present, otherwise adds it, so no new node indices appear.
+    /// Removing a set may leave a node without sets, dropping it from the game.
+    /// Returns the builder unchanged if it has fewer than two nodes.
+    pub fn mutate(mut self, edge_flips: usize, rng: &mut impl Rng) -> Builder {
+        let nodes = self.get_nodes();
+        if nodes.len() < 2 {
+            return self;
+        }
+        for _ in 0..edge_flips {
+            let a = rng.random_range(..nodes.len());
+            let b = (a + rng.random_range(1..nodes.len())) % nodes.len();
+            let (a, b) = (nodes[a.min(b)], nodes[a.max(b)]);
+            let existing = self
+                .hyperedges
+                .iter()
+                .position(|e| e.len() == 2 && e.contains(&a) && e.contains(&b));
+            match existing {
+                Some(i) => {
+                    self.hyperedges.remove(i);
+                }
+                None => self.hyperedges.push(vec![a, b]),
+            }
+        }
+        self
+    }
+    /// Subdivides every 2-node set `[a, b]` into a path `a - x1 - ... - x_times - b`
+    /// of 2-node sets through `times` new nodes.
+    ///
+    /// New nodes are numbered above `get_max_node()` and, for a builder from
+    /// [`Builder::from_labeled_hyperedges`], get fresh labels above all given ones.
+    /// Sets of other sizes are left unchanged.
+    pub fn subdivide(self, times: usize) -> Builder {
+        let mut next = self.get_max_node() + 1;
+        let mut hyperedges = Vec::with_capacity(self.hyperedges.len() * (times + 1));
+        for e in self.hyperedges {
+            if e.len() != 2 {
+                hyperedges.push(e);
+                continue;
+            }
+            let mut path = vec![e[0]];
+            path.extend(next..next + times);
+            path.push(e[1]);
+            next += times;
+            hyperedges.extend(path.windows(2).map(|w| w.to_vec()));
+        }
+        Builder {
+            hyperedges,
+            labels: self.labels,
+        }
+    }
+    /// Applies the Mycielski construction to a graph.
+    ///
+    /// For every node `v` a copy `u` is added, adjacent to the neighbours of `v`,
+    /// and a new node `w` is connected to all copies. Applied to a single edge
+    /// this yields the 5-cycle.
+    ///
+    /// Panics if a set contains more than two nodes.
+    pub fn mycielskian(mut self) -> Builder {
+        assert!(
+            self.hyperedges.iter().all(|e| e.len() <= 2),
+            "the Mycielski construction requires sets of at most two nodes"
+        );
+        let nodes = self.get_nodes();
+        let shift = self.get_max_node() + 1;
+        let w = 2 * shift;
+        let old_edges: Vec<Vec<usize>> = self
+            .hyperedges
+            .iter()
+            .filter(|e| e.len() == 2)
+            .cloned()
+            .collect();
+        for e in old_edges {
+            self.hyperedges.push(vec![e[0] + shift, e[1]]);
+            self.hyperedges.push(vec![e[0], e[1] + shift]);
+        }
+        for node in nodes {
+            self.hyperedges.push(vec![node + shift, w]);
+        }
+        self
+    }
 }
 #[cfg(test)]
 mod tests {
     use super::*;
+    use evaluator::Evaluator;
 
     #[test]
     fn test_empty_unit_heap() {
         let empty = Builder::empty();
-        assert_eq!(empty.hyperedges, vec![vec![]]);
-        assert_eq!(empty.get_nodes(), vec![]);
+        assert_eq!(empty.hyperedges, vec![Vec::<usize>::new()]);
+        assert_eq!(empty.get_nodes(), Vec::<usize>::new());
 
         let unit = Builder::unit();
         assert_eq!(unit.hyperedges, vec![vec![0]]);
@@ -220,7 +825,7 @@ mod tests {
     #[test]
     fn test_kayles() {
         let g0 = Builder::kayles(0);
-        assert_eq!(g0.hyperedges, vec![vec![]]);
+        assert_eq!(g0.hyperedges, vec![Vec::<usize>::new()]);
 
         let g1 = Builder::kayles(1);
         assert_eq!(g1.hyperedges, vec![vec![0]]);
@@ -231,6 +836,61 @@ mod tests {
         assert_eq!(nodes, vec![0, 1, 2]);
     }
 
+    #[test]
+    fn test_path_and_star() {
+        assert_eq!(Builder::path(5), Builder::kayles(5));
+
+        assert_eq!(Builder::star(0), Builder::empty());
+        assert_eq!(Builder::star(1), Builder::unit());
+        let star = Builder::star(5);
+        assert_eq!(star.hyperedges.len(), 4);
+        assert!(
+            star.hyperedges
+                .iter()
+                .all(|e| e.len() == 2 && e.contains(&0))
+        );
+        assert_eq!(star.get_nodes(), vec![0, 1, 2, 3, 4]);
+        assert_ne!(star.build_one(), Builder::path(5).build_one());
+    }
+
+    #[test]
+    fn test_cycle() {
+        assert_eq!(Builder::cycle(0), Builder::empty());
+        assert_eq!(Builder::cycle(1), Builder::unit());
+        assert_eq!(Builder::cycle(2), Builder::heap(2));
+        for size in 3..8 {
+            assert_eq!(Builder::cycle(size).hyperedges.len(), size);
+        }
+
+        // Shifting the node labels by `k` gives the same cycle.
+        for size in 3..8 {
+            let cycle = Builder::cycle(size).build_one().unwrap();
+            for k in 1..2 * size {
+                let shifted = Builder::from_hyperedges(
+                    (0..size).map(|i| vec![i + k, (i + 1) % size + k]).collect(),
+                )
+                .build_one()
+                .unwrap();
+                assert_eq!(shifted.nodes().len(), size);
+                assert_eq!(shifted, cycle);
+                assert_eq!(shifted.canonical_bytes(), cycle.canonical_bytes());
+            }
+        }
+    }
+
+    #[test]
+    fn test_complete_graph() {
+        assert_eq!(Builder::complete_graph(0), Builder::empty());
+        assert_eq!(Builder::complete_graph(1), Builder::unit());
+        for n in 2..7 {
+            assert_eq!(Builder::complete_graph(n).hyperedges.len(), n * (n - 1) / 2);
+        }
+        assert_eq!(
+            Builder::complete_graph(4).build_one(),
+            Builder::hyper_tetrahedron(3).build_one()
+        );
+    }
+
     #[test]
     fn test_connect_unit_to_all() {
         let base = Builder::unit();
@@ -310,6 +970,469 @@ mod tests {
         assert_eq!(one_game.unwrap().nr_nodes(), 1);
     }
 
+    #[test]
+    fn test_from_game() {
+        let g = Builder::kayles(3).build_one().unwrap();
+        let rebuilt = Builder::from_game(&g).build_one().unwrap();
+        assert_eq!(rebuilt, g);
+
+        let heap = Builder::heap(3).build_one().unwrap();
+        let extruded = Builder::from_game(&heap).extrude(2).build_one().unwrap();
+        assert_eq!(extruded, Builder::rect(3, 2).build_one().unwrap());
+    }
+
+    #[test]
+    fn test_build_one() {
+        assert_eq!(Builder::empty().build_one(), None);
+        assert_eq!(Builder::from_hyperedges(vec![]).build_one(), None);
+
+        let unit = Builder::unit().build_one().unwrap();
+        assert_eq!(unit.nr_nodes(), 1);
+        assert_eq!(Builder::unit().build(), vec![unit]);
+
+        let connected = Builder::kayles(4);
+        assert_eq!(
+            Builder::kayles(4).build_one(),
+            connected.build().into_iter().next()
+        );
+
+        let largest = Builder::heap(2)
+            .sum(Builder::kayles(5))
+            .sum(Builder::heap(3))
+            .build_one()
+            .unwrap();
+        assert_eq!(largest, Builder::kayles(5).build_one().unwrap());
+
+        let parts = Builder::heap(3).sum(Builder::heap(3)).build();
+        let tie = Builder::heap(3).sum(Builder::heap(3)).build_one().unwrap();
+        assert_eq!(tie.nodes(), parts.last().unwrap().nodes());
+    }
+
+    #[test]
+    fn test_from_labeled_hyperedges() {
+        let g = Builder::from_labeled_hyperedges(vec![vec![100, 500], vec![500, 900]])
+            .build_one()
+            .unwrap();
+        let mut nodes = g.nodes().to_vec();
+        nodes.sort();
+        assert_eq!(nodes, vec![100, 500, 900]);
+        assert_eq!(g, Builder::kayles(3).build_one().unwrap());
+
+        let rebuilt = Builder::from_game(&g).build_one().unwrap();
+        assert_eq!(rebuilt.nodes(), g.nodes());
+
+        let summed = Builder::from_labeled_hyperedges(vec![vec![7, 300]]).sum(Builder::unit());
+        let mut labels: Vec<usize> = summed
+            .build()
+            .iter()
+            .flat_map(|p| p.nodes().to_vec())
+            .collect();
+        labels.sort();
+        assert_eq!(labels, vec![7, 300, 301]);
+    }
+
+    #[test]
+    fn test_from_edge_list() {
+        let triangle = Builder::from_edge_list(3, &[(0, 1), (1, 2), (2, 0)]);
+        assert_eq!(
+            triangle.hyperedges,
+            vec![vec![0, 1], vec![1, 2], vec![2, 0]]
+        );
+        assert_eq!(triangle.build_one(), Builder::cycle(3).build_one());
+
+        let with_isolated = Builder::from_edge_list(3, &[(0, 1)]);
+        assert_eq!(with_isolated.hyperedges, vec![vec![0, 1], vec![2]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_from_edge_list_out_of_range() {
+        Builder::from_edge_list(2, &[(0, 2)]);
+    }
+
+    #[test]
+    fn test_from_adjacency_matrix() {
+        let matrix = vec![
+            vec![false, true, true, false],
+            vec![true, false, false, false],
+            vec![true, false, false, true],
+            vec![false, false, true, false],
+        ];
+        let g = Builder::from_adjacency_matrix(&matrix);
+        assert_eq!(g.hyperedges, vec![vec![0, 1], vec![0, 2], vec![2, 3]]);
+        assert_eq!(
+            g.build_one(),
+            Builder::from_edge_list(4, &[(1, 0), (0, 2), (2, 3)]).build_one()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "not symmetric")]
+    fn test_from_adjacency_matrix_asymmetric() {
+        Builder::from_adjacency_matrix(&[vec![false, true], vec![false, false]]);
+    }
+
+    #[test]
+    fn test_from_dimacs_like() {
+        let input = "3\n0 1\n\n1 2\n";
+        let b = Builder::from_dimacs_like(input.as_bytes()).unwrap();
+        assert_eq!(b, Builder::kayles(3));
+
+        let parse_error = |input: &str| {
+            let err = Builder::from_dimacs_like(input.as_bytes()).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+            *err.into_inner().unwrap().downcast::<ParseError>().unwrap()
+        };
+        assert_eq!(
+            parse_error("2\n0 1\n\n0 2\n"),
+            ParseError {
+                line: 4,
+                kind: ParseErrorKind::NodeOutOfRange {
+                    node: 2,
+                    node_count: 2
+                }
+            }
+        );
+        assert_eq!(
+            parse_error("3\n0 y\n"),
+            ParseError {
+                line: 2,
+                kind: ParseErrorKind::InvalidNode("y".to_string())
+            }
+        );
+        assert_eq!(
+            parse_error("x\n"),
+            ParseError {
+                line: 1,
+                kind: ParseErrorKind::InvalidNodeCount("x".to_string())
+            }
+        );
+        assert_eq!(parse_error("").kind, ParseErrorKind::MissingNodeCount);
+    }
+
+    #[test]
+    fn test_random_tree() {
+        let t = Builder::random_tree(12, 42);
+        assert_eq!(t.hyperedges.len(), 11);
+        assert_eq!(t.get_nodes(), (0..12).collect::<Vec<_>>());
+        assert_eq!(t, Builder::random_tree(12, 42));
+        assert_eq!(t.build().len(), 1);
+    }
+
+    #[test]
+    fn test_mutate() {
+        let mut rng = StdRng::seed_from_u64(7);
+        assert_eq!(Builder::rect(3, 3).mutate(0, &mut rng), Builder::rect(3, 3));
+
+        let original = Builder::kayles(6);
+        let mutated = Builder::kayles(6).mutate(1, &mut rng);
+        let changed = original
+            .hyperedges
+            .iter()
+            .filter(|e| !mutated.hyperedges.contains(e))
+            .count()
+            + mutated
+                .hyperedges
+                .iter()
+                .filter(|e| !original.hyperedges.contains(e))
+                .count();
+        assert_eq!(changed, 1);
+
+        for _ in 0..20 {
+            let mutated = Builder::kayles(6).mutate(10, &mut rng);
+            assert!(mutated.get_nodes().iter().all(|&n| n < 6));
+        }
+        assert_eq!(Builder::unit().mutate(3, &mut rng), Builder::unit());
+    }
+
+    #[test]
+    fn test_subdivide() {
+        let subdivided = Builder::heap(2).subdivide(1);
+        assert_eq!(subdivided.hyperedges, vec![vec![0, 2], vec![2, 1]]);
+        assert_eq!(subdivided.build_one(), Builder::kayles(3).build_one());
+
+        assert_eq!(Builder::kayles(3).subdivide(0), Builder::kayles(3));
+        assert_eq!(
+            Builder::kayles(3).subdivide(2).build_one(),
+            Builder::kayles(7).build_one()
+        );
+        let star = Builder::star(4).subdivide(1);
+        assert_eq!(star.hyperedges.len(), 6);
+        assert_eq!(star.get_nodes().len(), 7);
+        assert_eq!(Builder::heap(3).subdivide(2), Builder::heap(3));
+
+        let labelled = Builder::from_labeled_hyperedges(vec![vec![100, 500]])
+            .subdivide(1)
+            .build_one()
+            .unwrap();
+        let mut labels = labelled.nodes().to_vec();
+        labels.sort();
+        assert_eq!(labels, vec![100, 500, 501]);
+    }
+
+    #[test]
+    fn test_mycielskian() {
+        // A connected graph on 5 nodes where every node has degree 2 is the 5-cycle
+        let c5 = Builder::heap(2).mycielskian();
+        assert_eq!(c5.get_nodes().len(), 5);
+        assert_eq!(c5.hyperedges.len(), 5);
+        for node in c5.get_nodes() {
+            assert_eq!(
+                c5.hyperedges.iter().filter(|e| e.contains(&node)).count(),
+                2
+            );
+        }
+        assert_eq!(c5.build().len(), 1);
+
+        // The Grötzsch graph has 11 nodes and 20 edges
+        let grotzsch = Builder::heap(2).mycielskian().mycielskian();
+        assert_eq!(grotzsch.get_nodes().len(), 11);
+        assert_eq!(grotzsch.hyperedges.len(), 20);
+    }
+
+    #[test]
+    fn test_build_order_is_deterministic() {
+        let builder = || {
+            Builder::heap(2)
+                .sum(Builder::kayles(3))
+                .sum(Builder::heap(2))
+                .sum(Builder::rect(2, 2))
+        };
+        let printed = |parts: Vec<TakingGame>| -> Vec<String> {
+            parts.iter().map(|p| p.to_string()).collect()
+        };
+        let expected = printed(builder().build());
+        assert_eq!(expected.len(), 4);
+        for _ in 0..20 {
+            assert_eq!(printed(builder().build()), expected);
+        }
+    }
+
+    #[test]
+    fn test_complete_bipartite() {
+        let k23 = Builder::complete_bipartite(2, 3);
+        assert_eq!(k23.hyperedges.len(), 6);
+        assert!(k23.hyperedges.iter().all(|e| e[0] < 2 && e[1] >= 2));
+        assert_eq!(k23.build().len(), 1);
+
+        assert_eq!(
+            Builder::complete_bipartite(3, 3).build_one(),
+            Builder::turan(6, 2).build_one()
+        );
+        assert_eq!(Builder::complete_bipartite(0, 3).build().len(), 3);
+        assert_eq!(Builder::complete_bipartite(0, 0), Builder::empty());
+    }
+
+    #[test]
+    fn test_turan() {
+        let evaluator = Evaluator::new();
+        let octahedron = Builder::turan(6, 3);
+        assert_eq!(octahedron.hyperedges.len(), 12);
+        let octahedron = octahedron.build_one().unwrap();
+        assert!(octahedron.find_symmetry().is_some());
+        assert_eq!(evaluator.get_nimber(&octahedron), Some(0));
+
+        let k32 = Builder::turan(5, 2).build_one().unwrap();
+        assert!(k32.find_symmetry().is_none());
+        assert!(evaluator.get_nimber(&k32).is_some());
+
+        assert_eq!(Builder::turan(3, 1).build().len(), 3);
+    }
+
+    #[test]
+    fn test_sum() {
+        let sum = Builder::heap(2).sum(Builder::heap(3));
+        assert_eq!(sum.hyperedges, vec![vec![0, 1], vec![2, 3, 4]]);
+        let mut sizes: Vec<usize> = sum.build().iter().map(|p| p.nr_nodes()).collect();
+        sizes.sort();
+        assert_eq!(sizes, vec![2, 3]);
+
+        let labelled = Builder::heap(2).sum(Builder::from_labeled_hyperedges(vec![vec![70, 90]]));
+        let mut edges: Vec<Vec<usize>> = labelled
+            .build()
+            .iter()
+            .flat_map(|p| p.labeled_hyperedges())
+            .collect();
+        edges.sort();
+        assert_eq!(edges, vec![vec![0, 1], vec![72, 92]]);
+
+        let labelled = Builder::from_labeled_hyperedges(vec![vec![70, 90]]).sum(Builder::heap(2));
+        let mut labels: Vec<usize> = labelled
+            .build()
+            .iter()
+            .flat_map(|p| p.nodes().to_vec())
+            .collect();
+        labels.sort();
+        assert_eq!(labels, vec![70, 90, 91, 92]);
+    }
+
+    #[test]
+    fn test_disjoint_sum_of() {
+        let evaluator = Evaluator::new();
+        let parts =
+            Builder::disjoint_sum_of(vec![Builder::heap(4), Builder::heap(5), Builder::heap(7)])
+                .build();
+        assert_eq!(parts.len(), 3);
+        assert_eq!(evaluator.get_nimber_by_parts(&parts), Some(4 ^ 5 ^ 7));
+        assert_eq!(Builder::disjoint_sum_of(vec![]), Builder::empty());
+    }
+
+    #[test]
+    fn test_disjoint_copies() {
+        let evaluator = Evaluator::new();
+        let parts = Builder::kayles(4).disjoint_copies(2).build();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(evaluator.get_nimber_by_parts(&parts), Some(0));
+
+        assert_eq!(Builder::heap(3).disjoint_copies(5).build().len(), 5);
+        assert_eq!(
+            Builder::heap(2).disjoint_copies(2),
+            Builder::heap(2).sum(Builder::heap(2))
+        );
+        assert_eq!(Builder::heap(2).disjoint_copies(0), Builder::empty());
+
+        let labelled = Builder::from_labeled_hyperedges(vec![vec![10, 20]]).disjoint_copies(3);
+        let mut labels: Vec<usize> = labelled
+            .build()
+            .iter()
+            .flat_map(|p| p.nodes().to_vec())
+            .collect();
+        labels.sort();
+        assert_eq!(labels, vec![10, 20, 31, 41, 52, 62]);
+    }
+
+    #[test]
+    fn test_cylinder() {
+        assert_eq!(Builder::cylinder(4, 3, true).hyperedges.len(), 20);
+        assert_eq!(Builder::cylinder(4, 3, false).hyperedges.len(), 21);
+        assert_eq!(Builder::grid_graph(4, 3, false, false).hyperedges.len(), 17);
+
+        // The 3x3 cylinder differs in value from the open 3x3 grid graph
+        let evaluator = Evaluator::new();
+        let cylinder = Builder::cylinder(3, 3, true).build_one().unwrap();
+        assert_eq!(evaluator.get_nimber(&cylinder), Some(3));
+        let open = Builder::grid_graph(3, 3, false, false).build_one().unwrap();
+        assert_eq!(evaluator.get_nimber(&open), Some(1));
+    }
+
+    #[test]
+    fn test_torus() {
+        let torus = Builder::torus(4, 3);
+        assert_eq!(torus.hyperedges.len(), 2 * 4 * 3);
+        assert_eq!(torus.get_nodes(), (0..12).collect::<Vec<_>>());
+        for node in 0..12 {
+            assert_eq!(
+                torus
+                    .hyperedges
+                    .iter()
+                    .filter(|e| e.contains(&node))
+                    .count(),
+                4
+            );
+        }
+
+        let game = Builder::torus(4, 4).build_one().unwrap();
+        assert!(game.find_symmetry().is_some());
+    }
+
+    #[test]
+    fn test_hexagonal_grid() {
+        assert_eq!(Builder::hexagonal_grid(0, 3), Builder::empty());
+        assert_eq!(Builder::hexagonal_grid(1, 1), Builder::unit());
+        assert_eq!(
+            Builder::hexagonal_grid(2, 2).hyperedges,
+            vec![vec![0, 1], vec![0, 2], vec![1, 2], vec![1, 3], vec![2, 3]]
+        );
+
+        for (rows, cols) in [(1, 4), (4, 1), (3, 3), (4, 5)] {
+            let parts = Builder::hexagonal_grid(rows, cols).build();
+            assert_eq!(parts.len(), 1);
+            assert_eq!(parts[0].nr_nodes(), rows * cols);
+        }
+
+        let hex = Builder::hexagonal_grid(3, 3);
+        let degree = |node: usize| hex.hyperedges.iter().filter(|e| e.contains(&node)).count();
+        assert_eq!(degree(4), 6);
+        assert!((0..9).all(|node| degree(node) <= 6));
+    }
+
+    #[test]
+    fn test_grid_with_holes() {
+        assert_eq!(
+            Builder::grid_with_holes(3, 3, &[]).build_one(),
+            Builder::rect(3, 3).build_one()
+        );
+
+        let center = Builder::grid_with_holes(3, 3, &[(1, 1)]).build();
+        assert_eq!(center.len(), 1);
+        assert_eq!(center[0].nr_nodes(), 8);
+
+        let middle_row = Builder::grid_with_holes(3, 3, &[(0, 1), (1, 1), (2, 1)]).build();
+        assert_eq!(middle_row.len(), 2);
+        assert!(middle_row.iter().all(|p| p.nr_nodes() == 3 && p.is_heap()));
+
+        assert_eq!(Builder::grid_with_holes(1, 1, &[(0, 0)]), Builder::empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "outside the 3x3 board")]
+    fn test_grid_with_holes_outside() {
+        Builder::grid_with_holes(3, 3, &[(3, 0)]);
+    }
+
+    #[test]
+    fn test_mobius_strip() {
+        let strip = Builder::mobius_strip(5, 3);
+        assert_eq!(strip.hyperedges.len(), 4 * 3 + 5 * 2 + 3);
+        assert!(strip.hyperedges.contains(&vec![4, 10]));
+        assert!(strip.hyperedges.contains(&vec![9, 5]));
+        assert_eq!(strip.build().len(), 1);
+
+        // The Möbius ladder on 8 nodes is not bipartite, unlike the cube.
+        let mobius = Builder::mobius_strip(4, 2).build_one().unwrap();
+        let prism = Builder::cylinder(4, 2, true).build_one().unwrap();
+        assert!(!mobius.is_isomorphic_to(&prism));
+        assert_eq!(Builder::mobius_strip(0, 3), Builder::empty());
+    }
+
+    #[test]
+    fn test_king_grid() {
+        assert_eq!(Builder::king_grid(0, 2), Builder::empty());
+        assert_eq!(Builder::king_grid(1, 1), Builder::unit());
+        for (x, y) in [(3, 3), (2, 5), (4, 3)] {
+            let king = Builder::king_grid(x, y);
+            assert_eq!(
+                king.hyperedges.len(),
+                (x - 1) * y + x * (y - 1) + 2 * (x - 1) * (y - 1)
+            );
+        }
+
+        let king = Builder::king_grid(3, 3);
+        assert_eq!(king.hyperedges.len(), 20);
+        let degree = |node: usize| king.hyperedges.iter().filter(|e| e.contains(&node)).count();
+        assert_eq!(degree(0), 3);
+        assert_eq!(degree(1), 5);
+        assert_eq!(degree(4), 8);
+    }
+
+    #[test]
+    fn test_generalized_petersen() {
+        let petersen = Builder::generalized_petersen(5, 2);
+        assert_eq!(petersen.get_nodes().len(), 10);
+        assert_eq!(petersen.hyperedges.len(), 15);
+        for node in petersen.get_nodes() {
+            let degree = petersen
+                .hyperedges
+                .iter()
+                .filter(|e| e.contains(&node))
+                .count();
+            assert_eq!(degree, 3);
+        }
+        let petersen = petersen.build_one().unwrap();
+        let evaluator = Evaluator::new();
+        assert_eq!(evaluator.get_nimber(&petersen), Some(1));
+    }
+
     #[test]
     fn test_rand() {
         let r = Builder::rand(5, 3, 1, 3);
@@ -0,0 +1,108 @@
+use std::fmt::Display;
+
+use super::Builder;
+
+/// An error of [`Builder::from_str_format`] or [`Builder::from_dimacs_like`] input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The 1-based line of the error.
+    pub line: usize,
+    /// What is wrong with the line.
+    pub kind: ParseErrorKind,
+}
+
+/// The reason a line of text input was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The input has no node count header.
+    MissingNodeCount,
+    /// The node count header is not a non-negative integer.
+    InvalidNodeCount(String),
+    /// A token is not a node index.
+    InvalidNode(String),
+    /// A node index is not below the node count of the header.
+    NodeOutOfRange { node: usize, node_count: usize },
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            ParseErrorKind::MissingNodeCount => write!(f, "missing node count"),
+            ParseErrorKind::InvalidNodeCount(token) => write!(f, "invalid node count {token:?}"),
+            ParseErrorKind::InvalidNode(token) => write!(f, "invalid node {token:?}"),
+            ParseErrorKind::NodeOutOfRange { node, node_count } => {
+                write!(f, "node {node} out of range for {node_count} nodes")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses the node indices of one hyperedge, separated by whitespace or commas.
+///
+/// `line` is the 1-based line number reported in errors.
+pub(super) fn parse_edge_line(text: &str, line: usize) -> Result<Vec<usize>, ParseError> {
+    text.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|token| !token.is_empty())
+        .map(|token| {
+            token.parse().map_err(|_| ParseError {
+                line,
+                kind: ParseErrorKind::InvalidNode(token.to_string()),
+            })
+        })
+        .collect()
+}
+
+impl Builder {
+    /// Parses a `Builder` from a line-based text format.
+    ///
+    /// Every non-blank line lists the node indices of one hyperedge, separated by
+    /// whitespace or commas. Unlike [`Builder::from_dimacs_like`] there is no
+    /// node count header.
+    pub fn from_str_format(s: &str) -> Result<Builder, ParseError> {
+        let mut hyperedges = vec![];
+        for (i, line) in s.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            hyperedges.push(parse_edge_line(line, i + 1)?);
+        }
+        Ok(Builder::from_hyperedges(hyperedges))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str_format() {
+        let parsed = Builder::from_str_format("0 1\n1, 2\n").unwrap();
+        assert_eq!(
+            parsed,
+            Builder::from_hyperedges(vec![vec![0, 1], vec![1, 2]])
+        );
+
+        let spaced = Builder::from_str_format("\n  0,1 ,2\n\n3\t4\n").unwrap();
+        assert_eq!(
+            spaced,
+            Builder::from_hyperedges(vec![vec![0, 1, 2], vec![3, 4]])
+        );
+    }
+
+    #[test]
+    fn test_from_str_format_rejects_invalid_nodes() {
+        let err = Builder::from_str_format("0 1\n\n1 x").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError {
+                line: 3,
+                kind: ParseErrorKind::InvalidNode("x".to_string())
+            }
+        );
+        assert_eq!(err.to_string(), "line 3: invalid node \"x\"");
+        assert!(Builder::from_str_format("-1 2").is_err());
+    }
+}
@@ -0,0 +1,245 @@
+use crate::hypergraph::{Set, StructuredHypergraph};
+
+impl<E> StructuredHypergraph<E>
+where
+    E: Set,
+{
+    /// Returns the hyperedges under a labelling that only depends on the isomorphism class.
+    ///
+    /// Each hyperedge is a sorted list of node indices, and the list of hyperedges
+    /// is sorted as well, so two hypergraphs are isomorphic iff their canonical
+    /// forms are equal. The labelling is found by individualization-refinement:
+    /// colour refinement is applied until it is stable, then every node of the
+    /// first non-singleton colour class is individualized in turn and the
+    /// lexicographically smallest relabelled edge list over all branches is kept.
+    /// Branches that an already discovered automorphism maps onto an explored one
+    /// are skipped, which keeps highly symmetric games tractable.
+    pub fn canonical_form(&self) -> Vec<Vec<usize>> {
+        let mut search = CanonicalSearch {
+            edges: self
+                .hyperedges()
+                .iter()
+                .map(|e| e.iter().collect())
+                .collect(),
+            dual: self.dual(),
+            best: None,
+            automorphisms: vec![],
+        };
+        search.search(vec![0; self.nr_nodes()], &mut vec![]);
+        search.best.map_or_else(Vec::new, |(form, _)| form)
+    }
+}
+
+struct CanonicalSearch<'a> {
+    edges: Vec<Vec<usize>>,
+    dual: &'a [Vec<usize>],
+    /// The smallest relabelled edge list found so far and the labelling producing it.
+    best: Option<(Vec<Vec<usize>>, Vec<usize>)>,
+    automorphisms: Vec<Vec<usize>>,
+}
+
+impl CanonicalSearch<'_> {
+    fn search(&mut self, mut colours: Vec<usize>, fixed: &mut Vec<usize>) {
+        self.refine(&mut colours);
+        let mut sizes = vec![0; colours.len()];
+        colours.iter().for_each(|&c| sizes[c] += 1);
+        let Some(cell) = sizes.iter().position(|&size| size > 1) else {
+            self.visit_leaf(colours);
+            return;
+        };
+        let mut explored: Vec<usize> = vec![];
+        for node in (0..colours.len()).filter(|&n| colours[n] == cell) {
+            if explored.iter().any(|&e| self.same_orbit(e, node, fixed)) {
+                continue;
+            }
+            explored.push(node);
+            fixed.push(node);
+            self.search(rank(&individualize(&colours, node)), fixed);
+            fixed.pop();
+        }
+    }
+
+    /// Refines `colours` until every colour class is equitable.
+    ///
+    /// Colours are ranks of keys built only from colours, so the refinement commutes
+    /// with relabelling the nodes, and a class is only ever split, never merged.
+    fn refine(&self, colours: &mut Vec<usize>) {
+        let mut classes = class_count(colours);
+        loop {
+            let edge_colours = rank(
+                &self
+                    .edges
+                    .iter()
+                    .map(|e| sorted(e.iter().map(|&n| colours[n])))
+                    .collect::<Vec<_>>(),
+            );
+            *colours = rank(
+                &self
+                    .dual
+                    .iter()
+                    .enumerate()
+                    .map(|(n, edges)| (colours[n], sorted(edges.iter().map(|&e| edge_colours[e]))))
+                    .collect::<Vec<_>>(),
+            );
+            let refined = class_count(colours);
+            if refined == classes {
+                return;
+            }
+            classes = refined;
+        }
+    }
+
+    /// Compares the edge list relabelled by the discrete colouring `labelling` with the best one.
+    fn visit_leaf(&mut self, labelling: Vec<usize>) {
+        let form = sorted(
+            self.edges
+                .iter()
+                .map(|e| sorted(e.iter().map(|&n| labelling[n]))),
+        );
+        match &self.best {
+            Some((best, best_labelling)) if *best == form => {
+                let mut node_of_label = vec![0; labelling.len()];
+                best_labelling
+                    .iter()
+                    .enumerate()
+                    .for_each(|(n, &label)| node_of_label[label] = n);
+                let automorphism = labelling.iter().map(|&l| node_of_label[l]).collect();
+                self.automorphisms.push(automorphism);
+            }
+            Some((best, _)) if *best < form => {}
+            _ => self.best = Some((form, labelling)),
+        }
+    }
+
+    /// Returns true if an automorphism fixing every node of `fixed` maps `from` to `to`,
+    /// considering the group generated by the automorphisms found so far.
+    fn same_orbit(&self, from: usize, to: usize, fixed: &[usize]) -> bool {
+        let generators: Vec<&Vec<usize>> = self
+            .automorphisms
+            .iter()
+            .filter(|a| fixed.iter().all(|&n| a[n] == n))
+            .collect();
+        let mut orbit = vec![from];
+        let mut i = 0;
+        while i < orbit.len() {
+            for a in &generators {
+                let image = a[orbit[i]];
+                if !orbit.contains(&image) {
+                    orbit.push(image);
+                }
+            }
+            i += 1;
+        }
+        orbit.contains(&to)
+    }
+}
+
+/// Gives `node` a colour of its own, ordered just before the rest of its class.
+fn individualize(colours: &[usize], node: usize) -> Vec<(usize, bool)> {
+    colours
+        .iter()
+        .enumerate()
+        .map(|(n, &c)| (c, n != node))
+        .collect()
+}
+
+/// Replaces every key by its rank among the distinct keys.
+fn rank<K: Ord + Clone>(keys: &[K]) -> Vec<usize> {
+    let mut distinct = sorted(keys.iter().cloned());
+    distinct.dedup();
+    keys.iter()
+        .map(|k| distinct.partition_point(|d| d < k))
+        .collect()
+}
+
+fn class_count(colours: &[usize]) -> usize {
+    colours.iter().max().map_or(0, |max| max + 1)
+}
+
+fn sorted<T: Ord>(items: impl Iterator<Item = T>) -> Vec<T> {
+    let mut items: Vec<T> = items.collect();
+    items.sort_unstable();
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng, seq::SliceRandom};
+
+    use crate::{
+        builder::Builder,
+        hypergraph::{Bitset128, Set, StructuredHypergraph},
+    };
+
+    fn graph(edges: &[Vec<usize>]) -> StructuredHypergraph<Bitset128> {
+        let edges = edges.iter().map(|e| Bitset128::from_slice(e)).collect();
+        StructuredHypergraph::from_hyperedges_unsorted(edges)
+    }
+
+    #[test]
+    fn test_canonical_form_of_relabelled_games() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for builder in [
+            Builder::cycle(6),
+            Builder::complete_bipartite(3, 3),
+            Builder::hyper_cube(3, 2),
+            Builder::generalized_petersen(5, 2),
+            Builder::torus(4, 4),
+            Builder::rect(3, 4),
+            Builder::hyper_tetrahedron(3),
+        ] {
+            let game = builder.build_one().unwrap();
+            let form = graph(&game.labeled_hyperedges()).canonical_form();
+            for _ in 0..20 {
+                let mut labels: Vec<usize> = (0..game.nr_nodes()).collect();
+                labels.shuffle(&mut rng);
+                let mut edges: Vec<Vec<usize>> = game
+                    .labeled_hyperedges()
+                    .iter()
+                    .map(|e| e.iter().map(|&n| labels[n]).collect())
+                    .collect();
+                edges.shuffle(&mut rng);
+                assert_eq!(graph(&edges).canonical_form(), form);
+            }
+        }
+    }
+
+    #[test]
+    fn test_canonical_form_separates_games() {
+        let cycle = graph(&Builder::cycle(8).build_one().unwrap().labeled_hyperedges());
+        let cube = graph(
+            &Builder::hyper_cube(3, 2)
+                .build_one()
+                .unwrap()
+                .labeled_hyperedges(),
+        );
+        let prism = graph(
+            &Builder::generalized_petersen(4, 1)
+                .build_one()
+                .unwrap()
+                .labeled_hyperedges(),
+        );
+        let triangles = graph(&[
+            vec![0, 1],
+            vec![1, 2],
+            vec![0, 2],
+            vec![3, 4],
+            vec![4, 5],
+            vec![3, 5],
+        ]);
+        let hexagon = graph(&[
+            vec![0, 1],
+            vec![1, 2],
+            vec![2, 3],
+            vec![3, 4],
+            vec![4, 5],
+            vec![0, 5],
+        ]);
+
+        // The prism over a square is the cube.
+        assert_eq!(cube.canonical_form(), prism.canonical_form());
+        assert_ne!(cycle.canonical_form(), cube.canonical_form());
+        assert_ne!(triangles.canonical_form(), hexagon.canonical_form());
+        assert_eq!(hexagon.canonical_form()[0], vec![0, 1]);
+    }
+}
@@ -0,0 +1,115 @@
+use evaluator::Evaluator;
+
+use crate::hypergraph::{Bitset128, Set, StructuredHypergraph};
+use crate::taking_game::TakingGame;
+
+/// An object-safe view of a [`StructuredHypergraph`], independent of its set type.
+///
+/// This lets hypergraphs with different backing sets, e.g. [`Bitset128`] for
+/// small games and [`DynamicBitset`] for games beyond 128 nodes, live in one
+/// `Vec<Box<dyn DynHypergraph>>`. The price is a virtual call per method, and
+/// hyperedges are returned as node lists instead of sets. Nimbers are not
+/// searched on this view; games are converted into [`TakingGame`]s and evaluated
+/// by the shared [`Evaluator`], so only games of at most 128 nodes can be evaluated.
+///
+/// [`DynamicBitset`]: crate::hypergraph::DynamicBitset
+pub trait DynHypergraph {
+    /// Returns the number of nodes.
+    fn nr_nodes(&self) -> usize;
+    /// Returns the original node labels.
+    fn nodes(&self) -> &[usize];
+    /// Returns the canonical hyperedges as lists of canonical node indices.
+    fn hyperedges(&self) -> Vec<Vec<usize>>;
+
+    /// Converts the hypergraph into the components of a [`TakingGame`], keeping the node labels.
+    ///
+    /// Returns `None` if it has more nodes than a [`Bitset128`] holds.
+    fn to_taking_game(&self) -> Option<Vec<TakingGame>> {
+        (self.nr_nodes() <= Bitset128::CAPACITY).then(|| {
+            TakingGame::from_hyperedges_with_labels(self.hyperedges(), self.nodes().to_vec())
+        })
+    }
+
+    /// Computes the nimber with `eval`, see [`DynHypergraph::to_taking_game`].
+    ///
+    /// Returns `None` if the game does not fit into a [`TakingGame`] or could not be evaluated.
+    fn nimber(&self, eval: &Evaluator<TakingGame>) -> Option<usize> {
+        eval.get_nimber_by_parts(&self.to_taking_game()?)
+    }
+}
+
+impl<E> DynHypergraph for StructuredHypergraph<E>
+where
+    E: Set + 'static,
+{
+    fn nr_nodes(&self) -> usize {
+        StructuredHypergraph::nr_nodes(self)
+    }
+
+    fn nodes(&self) -> &[usize] {
+        StructuredHypergraph::nodes(self)
+    }
+
+    fn hyperedges(&self) -> Vec<Vec<usize>> {
+        StructuredHypergraph::hyperedges(self)
+            .iter()
+            .map(|e| e.iter().collect())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hypergraph::{Bitset128, DynamicBitset};
+
+    #[test]
+    fn test_mixed_set_types() {
+        let kayles: Vec<Bitset128> = (0..4).map(|i| Bitset128::from_slice(&[i, i + 1])).collect();
+        let large_heap = DynamicBitset::from_slice(&(200..206).collect::<Vec<_>>());
+        let games: Vec<Box<dyn DynHypergraph>> = vec![
+            Box::new(StructuredHypergraph::from_hyperedges(kayles).pop().unwrap()),
+            Box::new(
+                StructuredHypergraph::from_hyperedges(vec![large_heap])
+                    .pop()
+                    .unwrap(),
+            ),
+        ];
+        assert_eq!(games[0].nr_nodes(), 5);
+        assert_eq!(games[1].nr_nodes(), 6);
+        assert_eq!(games[1].nodes().iter().min(), Some(&200));
+        // Kayles on five nodes has nimber 4, a heap of six nodes nimber 6.
+        let eval = Evaluator::new();
+        let nimbers: Vec<Option<usize>> = games.iter().map(|g| g.nimber(&eval)).collect();
+        assert_eq!(nimbers, vec![Some(4), Some(6)]);
+
+        let too_large = DynamicBitset::from_slice(&(0..130).collect::<Vec<_>>());
+        let too_large = StructuredHypergraph::from_hyperedges(vec![too_large])
+            .pop()
+            .unwrap();
+        assert!(too_large.to_taking_game().is_none());
+        assert_eq!(too_large.nimber(&eval), None);
+    }
+
+    #[test]
+    fn test_hyperedges_agree_across_set_types() {
+        let edges = [vec![0, 1, 2], vec![2, 3], vec![3, 4, 5]];
+        let small = StructuredHypergraph::from_hyperedges(
+            edges.iter().map(|e| Bitset128::from_slice(e)).collect(),
+        )
+        .pop()
+        .unwrap();
+        let large = StructuredHypergraph::from_hyperedges(
+            edges.iter().map(|e| DynamicBitset::from_slice(e)).collect(),
+        )
+        .pop()
+        .unwrap();
+        assert_eq!(
+            DynHypergraph::hyperedges(&small),
+            DynHypergraph::hyperedges(&large)
+        );
+        let eval = Evaluator::new();
+        assert_eq!(small.nimber(&eval), large.nimber(&eval));
+        assert_eq!(small.to_taking_game(), large.to_taking_game());
+    }
+}
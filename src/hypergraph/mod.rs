@@ -1,4 +1,7 @@
+mod canonical_form;
+mod dyn_hypergraph;
 mod set;
 mod structured_hypergraph;
-pub use set::{Bitset128, Set};
+pub use dyn_hypergraph::DynHypergraph;
+pub use set::{Bitset128, BitsetN, DynamicBitset, Set};
 pub use structured_hypergraph::StructuredHypergraph;
@@ -1,10 +1,21 @@
-use std::{hash::Hash, ops::Range};
+use std::{
+    cmp::Ordering,
+    hash::Hash,
+    ops::{BitAnd, BitOr, BitXor, Not, Range},
+};
 
 pub trait Set: Default + Sized {
     type Iter<'a>: Iterator<Item = usize> + 'a
     where
         Self: 'a;
 
+    /// The number of distinct elements the set can hold, elements range over `0..CAPACITY`.
+    const CAPACITY: usize;
+
+    fn capacity(&self) -> usize {
+        Self::CAPACITY
+    }
+
     fn from_slice(vec: &[usize]) -> Self;
     fn insert(&mut self, value: usize);
 
@@ -12,16 +23,25 @@ pub trait Set: Default + Sized {
     fn is_empty(&self) -> bool;
     fn iter(&self) -> Self::Iter<'_>;
 
+    /// Iterates over the elements, each paired with the number of elements still to come.
+    fn iter_with_remaining(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let len = self.len();
+        self.iter().enumerate().map(move |(i, e)| (e, len - i - 1))
+    }
+
     fn contains(&self, element: &usize) -> bool;
     fn union(&mut self, other: &Self);
     fn minus(&self, other: &Self) -> Self;
     fn is_subset(&self, other: &Self) -> bool;
     fn intersects(&self, other: &Self) -> bool;
+    fn intersection(&self, other: &Self) -> Self;
+    fn symmetric_difference(&self, other: &Self) -> Self;
 
     fn apply_node_map(&mut self, permutation: &[usize]);
     fn is_flattened(&self) -> bool;
     fn partition(&self, partitions: &[Range<usize>]) -> Vec<Self>;
     fn pop(&mut self) -> Option<usize>;
+    fn pop_lowest(&mut self) -> Option<usize>;
 }
 
 #[derive(Default, PartialEq, Eq, Debug, Clone, Hash, PartialOrd, Ord)]
@@ -32,6 +52,38 @@ impl Bitset128 {
     }
 }
 
+impl BitAnd for Bitset128 {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Bitset128(self.0 & rhs.0)
+    }
+}
+
+impl BitOr for Bitset128 {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Bitset128(self.0 | rhs.0)
+    }
+}
+
+impl BitXor for Bitset128 {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self {
+        Bitset128(self.0 ^ rhs.0)
+    }
+}
+
+impl Not for Bitset128 {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Bitset128(!self.0)
+    }
+}
+
 pub struct Bitset128Iter {
     bits: u128,
 }
@@ -55,7 +107,13 @@ impl Set for Bitset128 {
     where
         Self: 'a;
 
+    const CAPACITY: usize = 128;
+
     fn from_slice(vec: &[usize]) -> Self {
+        debug_assert!(
+            vec.iter().all(|&e| e < 128),
+            "{vec:?} does not fit into a Bitset128"
+        );
         let mut set = Bitset128::default();
         vec.iter().copied().for_each(|e| set.insert(e));
         set
@@ -85,6 +143,14 @@ impl Set for Bitset128 {
         (self.0 & other.0) != 0
     }
 
+    fn intersection(&self, other: &Self) -> Self {
+        Bitset128(self.0 & other.0)
+    }
+
+    fn symmetric_difference(&self, other: &Self) -> Self {
+        Bitset128(self.0 ^ other.0)
+    }
+
     fn apply_node_map(&mut self, permutation: &[usize]) {
         let mut new_bits = 0u128;
         for (new_idx, old_idx) in permutation.iter().enumerate() {
@@ -121,7 +187,17 @@ impl Set for Bitset128 {
         Some(val)
     }
 
+    fn pop_lowest(&mut self) -> Option<usize> {
+        if self.is_empty() {
+            return None;
+        }
+        let val = self.0.trailing_zeros() as usize;
+        self.0 &= !(1 << val);
+        Some(val)
+    }
+
     fn insert(&mut self, value: usize) {
+        debug_assert!(value < 128, "{value} does not fit into a Bitset128");
         self.0 |= 1 << value;
     }
 
@@ -133,10 +209,388 @@ impl Set for Bitset128 {
         (self.0 >> element) & 1 == 1
     }
 }
+/// A growable bitset backed by 64-bit words, for games with more than 128 nodes.
+///
+/// Trailing zero words are always trimmed, so equal sets have equal representations.
+#[derive(Default, PartialEq, Eq, Debug, Clone, Hash)]
+pub struct DynamicBitset {
+    words: Vec<u64>,
+}
+impl DynamicBitset {
+    fn word(&self, index: usize) -> u64 {
+        self.words.get(index).copied().unwrap_or(0)
+    }
+
+    fn trim(&mut self) {
+        while self.words.last() == Some(&0) {
+            self.words.pop();
+        }
+    }
+}
+
+/// Orders sets like the integers their bits represent, as for [`Bitset128`].
+impl Ord for DynamicBitset {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.words
+            .len()
+            .cmp(&other.words.len())
+            .then_with(|| self.words.iter().rev().cmp(other.words.iter().rev()))
+    }
+}
+
+impl PartialOrd for DynamicBitset {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Iterates over the set bits of a slice of 64-bit words, lowest first.
+pub struct WordIter<'a> {
+    words: &'a [u64],
+    index: usize,
+    current: u64,
+}
+
+impl Iterator for WordIter<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.current == 0 {
+            self.index += 1;
+            self.current = *self.words.get(self.index)?;
+        }
+        let tz = self.current.trailing_zeros() as usize;
+        self.current &= self.current - 1;
+        Some(self.index * 64 + tz)
+    }
+}
+
+impl Set for DynamicBitset {
+    type Iter<'a>
+        = WordIter<'a>
+    where
+        Self: 'a;
+
+    const CAPACITY: usize = usize::MAX;
+
+    fn from_slice(vec: &[usize]) -> Self {
+        let mut set = DynamicBitset::default();
+        vec.iter().copied().for_each(|e| set.insert(e));
+        set
+    }
+
+    fn insert(&mut self, value: usize) {
+        let index = value / 64;
+        if index >= self.words.len() {
+            self.words.resize(index + 1, 0);
+        }
+        self.words[index] |= 1 << (value % 64);
+    }
+
+    fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        WordIter {
+            words: &self.words,
+            index: 0,
+            current: self.word(0),
+        }
+    }
+
+    fn contains(&self, element: &usize) -> bool {
+        (self.word(element / 64) >> (element % 64)) & 1 == 1
+    }
+
+    fn union(&mut self, other: &Self) {
+        if other.words.len() > self.words.len() {
+            self.words.resize(other.words.len(), 0);
+        }
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word |= other;
+        }
+    }
+
+    fn minus(&self, other: &Self) -> Self {
+        let mut set = DynamicBitset {
+            words: (0..self.words.len())
+                .map(|i| self.words[i] & !other.word(i))
+                .collect(),
+        };
+        set.trim();
+        set
+    }
+
+    fn is_subset(&self, other: &Self) -> bool {
+        (0..self.words.len()).all(|i| self.words[i] & !other.word(i) == 0)
+    }
+
+    fn intersects(&self, other: &Self) -> bool {
+        self.words.iter().zip(&other.words).any(|(a, b)| a & b != 0)
+    }
+
+    fn intersection(&self, other: &Self) -> Self {
+        let mut set = DynamicBitset {
+            words: self
+                .words
+                .iter()
+                .zip(&other.words)
+                .map(|(a, b)| a & b)
+                .collect(),
+        };
+        set.trim();
+        set
+    }
+
+    fn symmetric_difference(&self, other: &Self) -> Self {
+        let mut set = DynamicBitset {
+            words: (0..self.words.len().max(other.words.len()))
+                .map(|i| self.word(i) ^ other.word(i))
+                .collect(),
+        };
+        set.trim();
+        set
+    }
+
+    fn apply_node_map(&mut self, permutation: &[usize]) {
+        let mut mapped = DynamicBitset::default();
+        for (new_idx, old_idx) in permutation.iter().enumerate() {
+            if self.contains(old_idx) {
+                mapped.insert(new_idx);
+            }
+        }
+        *self = mapped;
+    }
+
+    fn is_flattened(&self) -> bool {
+        match self.words.split_last() {
+            None => true,
+            Some((last, rest)) => {
+                rest.iter().all(|w| *w == u64::MAX) && last & last.wrapping_add(1) == 0
+            }
+        }
+    }
+
+    fn partition(&self, partitions: &[Range<usize>]) -> Vec<Self> {
+        partitions
+            .iter()
+            .map(|part| {
+                let mut set = DynamicBitset {
+                    words: self.words.clone(),
+                };
+                for (i, word) in set.words.iter_mut().enumerate() {
+                    let low = part.start.saturating_sub(i * 64).min(64);
+                    let high = part.end.saturating_sub(i * 64).min(64);
+                    let mask = match high.saturating_sub(low) {
+                        0 => 0,
+                        64 => u64::MAX,
+                        width => ((1u64 << width) - 1) << low,
+                    };
+                    *word &= mask;
+                }
+                set.trim();
+                set
+            })
+            .collect()
+    }
+
+    fn pop(&mut self) -> Option<usize> {
+        let last = self.words.last_mut()?;
+        let bit = 63 - last.leading_zeros() as usize;
+        *last &= !(1 << bit);
+        let val = (self.words.len() - 1) * 64 + bit;
+        self.trim();
+        Some(val)
+    }
+
+    fn pop_lowest(&mut self) -> Option<usize> {
+        let index = self.words.iter().position(|w| *w != 0)?;
+        let bit = self.words[index].trailing_zeros() as usize;
+        self.words[index] &= !(1 << bit);
+        self.trim();
+        Some(index * 64 + bit)
+    }
+}
+/// A fixed-size bitset of `LIMBS` 64-bit words, holding elements `0..64 * LIMBS`.
+///
+/// Unlike [`DynamicBitset`] it lives on the stack, so the capacity can be chosen
+/// per use without heap allocations during search.
+#[derive(PartialEq, Eq, Debug, Clone, Hash)]
+pub struct BitsetN<const LIMBS: usize>([u64; LIMBS]);
+
+impl<const LIMBS: usize> BitsetN<LIMBS> {
+    pub fn new(words: [u64; LIMBS]) -> Self {
+        BitsetN(words)
+    }
+
+    fn zip_with(&self, other: &Self, f: impl Fn(u64, u64) -> u64) -> Self {
+        BitsetN(std::array::from_fn(|i| f(self.0[i], other.0[i])))
+    }
+}
+
+impl<const LIMBS: usize> Default for BitsetN<LIMBS> {
+    fn default() -> Self {
+        BitsetN([0; LIMBS])
+    }
+}
+
+/// Orders sets like the integers their bits represent, as for [`Bitset128`].
+impl<const LIMBS: usize> Ord for BitsetN<LIMBS> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl<const LIMBS: usize> PartialOrd for BitsetN<LIMBS> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const LIMBS: usize> Set for BitsetN<LIMBS> {
+    type Iter<'a>
+        = WordIter<'a>
+    where
+        Self: 'a;
+
+    const CAPACITY: usize = 64 * LIMBS;
+
+    fn from_slice(vec: &[usize]) -> Self {
+        let mut set = BitsetN::default();
+        vec.iter().copied().for_each(|e| set.insert(e));
+        set
+    }
+
+    fn insert(&mut self, value: usize) {
+        debug_assert!(
+            value < Self::CAPACITY,
+            "{value} does not fit into a BitsetN<{LIMBS}>"
+        );
+        self.0[value / 64] |= 1 << (value % 64);
+    }
+
+    fn len(&self) -> usize {
+        self.0.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.iter().all(|w| *w == 0)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        WordIter {
+            words: &self.0,
+            index: 0,
+            current: self.0.first().copied().unwrap_or(0),
+        }
+    }
+
+    fn contains(&self, element: &usize) -> bool {
+        self.0
+            .get(element / 64)
+            .is_some_and(|w| (w >> (element % 64)) & 1 == 1)
+    }
+
+    fn union(&mut self, other: &Self) {
+        for (word, other) in self.0.iter_mut().zip(&other.0) {
+            *word |= other;
+        }
+    }
+
+    fn minus(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & !b)
+    }
+
+    fn is_subset(&self, other: &Self) -> bool {
+        self.0.iter().zip(&other.0).all(|(a, b)| a & !b == 0)
+    }
+
+    fn intersects(&self, other: &Self) -> bool {
+        self.0.iter().zip(&other.0).any(|(a, b)| a & b != 0)
+    }
+
+    fn intersection(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & b)
+    }
+
+    fn symmetric_difference(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a ^ b)
+    }
+
+    fn apply_node_map(&mut self, permutation: &[usize]) {
+        let mut mapped = BitsetN::default();
+        for (new_idx, old_idx) in permutation.iter().enumerate() {
+            if self.contains(old_idx) {
+                mapped.insert(new_idx);
+            }
+        }
+        *self = mapped;
+    }
+
+    fn is_flattened(&self) -> bool {
+        let mut full = true;
+        for word in &self.0 {
+            if full {
+                if *word != u64::MAX {
+                    if word & word.wrapping_add(1) != 0 {
+                        return false;
+                    }
+                    full = false;
+                }
+            } else if *word != 0 {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn partition(&self, partitions: &[Range<usize>]) -> Vec<Self> {
+        partitions
+            .iter()
+            .map(|part| {
+                BitsetN(std::array::from_fn(|i| {
+                    let low = part.start.saturating_sub(i * 64).min(64);
+                    let high = part.end.saturating_sub(i * 64).min(64);
+                    let mask = match high.saturating_sub(low) {
+                        0 => 0,
+                        64 => u64::MAX,
+                        width => ((1u64 << width) - 1) << low,
+                    };
+                    self.0[i] & mask
+                }))
+            })
+            .collect()
+    }
+
+    fn pop(&mut self) -> Option<usize> {
+        let index = self.0.iter().rposition(|w| *w != 0)?;
+        let bit = 63 - self.0[index].leading_zeros() as usize;
+        self.0[index] &= !(1 << bit);
+        Some(index * 64 + bit)
+    }
+
+    fn pop_lowest(&mut self) -> Option<usize> {
+        let index = self.0.iter().position(|w| *w != 0)?;
+        let bit = self.0[index].trailing_zeros() as usize;
+        self.0[index] &= !(1 << bit);
+        Some(index * 64 + bit)
+    }
+}
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_capacity() {
+        assert_eq!(Bitset128::CAPACITY, 128);
+        assert_eq!(Bitset128::default().capacity(), 128);
+    }
+
     #[test]
     fn test_len_and_is_empty() {
         let mut b = Bitset128(0);
@@ -155,6 +609,13 @@ mod tests {
         assert_eq!(collected, vec![1, 2, 4]);
     }
 
+    #[test]
+    fn test_iter_with_remaining() {
+        let b = Bitset128(0b10110);
+        let collected: Vec<(usize, usize)> = b.iter_with_remaining().collect();
+        assert_eq!(collected, vec![(1, 2), (2, 1), (4, 0)]);
+    }
+
     #[test]
     fn test_union() {
         let mut a = Bitset128(0b1010);
@@ -175,6 +636,31 @@ mod tests {
         assert!(!a.intersects(&c));
     }
 
+    #[test]
+    fn test_intersection_and_symmetric_difference() {
+        let a = Bitset128(0b1010);
+        let b = Bitset128(0b1110);
+        assert_eq!(a.intersection(&b), a);
+        assert_eq!(a.symmetric_difference(&b), Bitset128(0b0100));
+
+        let c = Bitset128(0b0001);
+        assert!(a.intersection(&c).is_empty());
+        assert_eq!(a.symmetric_difference(&c), Bitset128(0b1011));
+    }
+
+    #[test]
+    fn test_operators() {
+        let a = Bitset128(0b1010);
+        let b = Bitset128(0b0110);
+
+        let mut union = a.clone();
+        union.union(&b);
+        assert_eq!(a.clone() | b.clone(), union);
+        assert_eq!(a.clone() & b.clone(), Bitset128(0b0010));
+        assert_eq!(a.clone() ^ b.clone(), Bitset128(0b1100));
+        assert_eq!(a.clone() & !b.clone(), a.minus(&b));
+    }
+
     #[test]
     fn test_apply_node_map() {
         let mut b = Bitset128(0b1011); // bits 0,1,3
@@ -208,6 +694,23 @@ mod tests {
         assert_eq!(b.pop(), None);
         assert!(b.is_empty());
     }
+
+    #[test]
+    fn test_pop_lowest() {
+        let mut b = Bitset128(0b10110);
+        assert_eq!(b.pop_lowest(), Some(1));
+        assert_eq!(b.pop_lowest(), Some(2));
+        assert_eq!(b.pop_lowest(), Some(4));
+        assert_eq!(b.pop_lowest(), None);
+        assert!(b.is_empty());
+    }
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "does not fit into a Bitset128")]
+    fn test_insert_beyond_capacity() {
+        Bitset128::default().insert(128);
+    }
+
     #[test]
     fn test_contains() {
         let b = Bitset128::from_slice(&[1, 2, 3, 5, 8, 13, 21, 34]);
@@ -215,4 +718,171 @@ mod tests {
         assert!(b.contains(&34));
         assert!(!b.contains(&17));
     }
+
+    #[test]
+    fn test_dynamic_len_iter_and_contains() {
+        let mut b = DynamicBitset::default();
+        assert!(b.is_empty());
+        b.insert(3);
+        b.insert(130);
+        b.insert(200);
+        assert_eq!(b.len(), 3);
+        assert_eq!(b.iter().collect::<Vec<_>>(), vec![3, 130, 200]);
+        assert_eq!(
+            b.iter_with_remaining().collect::<Vec<_>>(),
+            vec![(3, 2), (130, 1), (200, 0)]
+        );
+        assert!(b.contains(&130));
+        assert!(!b.contains(&129));
+        assert!(!b.contains(&1000));
+        assert_eq!(DynamicBitset::from_slice(&[200, 3, 130]), b);
+    }
+
+    #[test]
+    fn test_dynamic_union_minus_and_subset() {
+        let mut a = DynamicBitset::from_slice(&[1, 140]);
+        let b = DynamicBitset::from_slice(&[140, 300]);
+        assert!(a.intersects(&b));
+        a.union(&b);
+        assert_eq!(a, DynamicBitset::from_slice(&[1, 140, 300]));
+        assert!(b.is_subset(&a));
+        assert!(!a.is_subset(&b));
+
+        let rest = a.minus(&b);
+        assert_eq!(rest, DynamicBitset::from_slice(&[1]));
+        assert!(!rest.intersects(&b));
+        assert!(a.minus(&a).is_empty());
+        assert_eq!(a.minus(&a), DynamicBitset::default());
+    }
+
+    #[test]
+    fn test_dynamic_intersection_and_symmetric_difference() {
+        let a = DynamicBitset::from_slice(&[1, 140]);
+        let b = DynamicBitset::from_slice(&[1, 140, 300]);
+        assert_eq!(a.intersection(&b), a);
+        assert_eq!(
+            a.symmetric_difference(&b),
+            DynamicBitset::from_slice(&[300])
+        );
+
+        let c = DynamicBitset::from_slice(&[2]);
+        assert!(b.intersection(&c).is_empty());
+        assert_eq!(b.intersection(&c), DynamicBitset::default());
+        assert_eq!(
+            c.symmetric_difference(&a),
+            DynamicBitset::from_slice(&[1, 2, 140])
+        );
+        assert_eq!(b.symmetric_difference(&b), DynamicBitset::default());
+    }
+
+    #[test]
+    fn test_dynamic_apply_node_map() {
+        let mut b = DynamicBitset::from_slice(&[0, 129]);
+        let mut permutation: Vec<usize> = (0..130).collect();
+        permutation.reverse();
+        b.apply_node_map(&permutation);
+        assert_eq!(b, DynamicBitset::from_slice(&[0, 129]));
+
+        let mut c = DynamicBitset::from_slice(&[1, 129]);
+        c.apply_node_map(&permutation);
+        assert_eq!(c, DynamicBitset::from_slice(&[0, 128]));
+    }
+
+    #[test]
+    fn test_dynamic_is_flattened() {
+        assert!(DynamicBitset::default().is_flattened());
+        assert!(DynamicBitset::from_slice(&(0..150).collect::<Vec<_>>()).is_flattened());
+        assert!(!DynamicBitset::from_slice(&[0, 1, 150]).is_flattened());
+        assert!(!DynamicBitset::from_slice(&(1..64).collect::<Vec<_>>()).is_flattened());
+    }
+
+    #[test]
+    fn test_dynamic_partition() {
+        let b = DynamicBitset::from_slice(&[1, 60, 63, 64, 127, 128, 200]);
+        let parts = b.partition(&[0..60, 60..130, 130..256]);
+        assert_eq!(parts[0], DynamicBitset::from_slice(&[1]));
+        assert_eq!(parts[1], DynamicBitset::from_slice(&[60, 63, 64, 127, 128]));
+        assert_eq!(parts[2], DynamicBitset::from_slice(&[200]));
+    }
+
+    #[test]
+    fn test_dynamic_pop() {
+        let mut b = DynamicBitset::from_slice(&[2, 130]);
+        assert_eq!(b.pop(), Some(130));
+        assert_eq!(b, DynamicBitset::from_slice(&[2]));
+        assert_eq!(b.pop(), Some(2));
+        assert_eq!(b.pop(), None);
+
+        let mut b = DynamicBitset::from_slice(&[2, 130]);
+        assert_eq!(b.pop_lowest(), Some(2));
+        assert_eq!(b.pop_lowest(), Some(130));
+        assert_eq!(b.pop_lowest(), None);
+        assert!(b.is_empty());
+    }
+
+    #[test]
+    fn test_dynamic_ord_matches_integer_order() {
+        let small = DynamicBitset::from_slice(&[0, 63]);
+        let large = DynamicBitset::from_slice(&[64]);
+        assert!(small < large);
+        assert_eq!(
+            Bitset128::from_slice(&[0, 63]).cmp(&Bitset128::from_slice(&[64])),
+            small.cmp(&large)
+        );
+    }
+
+    fn check_bitset_n<const LIMBS: usize>() {
+        type B<const L: usize> = BitsetN<L>;
+        let capacity = B::<LIMBS>::CAPACITY;
+        assert_eq!(capacity, 64 * LIMBS);
+        let last = capacity - 1;
+        let mid = capacity / 2;
+
+        let mut b = B::<LIMBS>::from_slice(&[1, mid - 1, mid, last]);
+        assert_eq!(b.len(), 4);
+        assert_eq!(b.iter().collect::<Vec<_>>(), vec![1, mid - 1, mid, last]);
+        assert!(b.contains(&mid) && !b.contains(&(mid + 1)) && !b.contains(&capacity));
+
+        let reverse: Vec<usize> = (0..capacity).rev().collect();
+        b.apply_node_map(&reverse);
+        assert_eq!(b, B::from_slice(&[0, mid - 1, mid, last - 1]));
+
+        let all: Vec<usize> = (0..capacity).collect();
+        assert!(B::<LIMBS>::default().is_flattened());
+        assert!(B::<LIMBS>::from_slice(&all).is_flattened());
+        assert!(B::<LIMBS>::from_slice(&all[..=mid]).is_flattened());
+        assert!(!B::<LIMBS>::from_slice(&[0, last]).is_flattened());
+        assert!(!B::<LIMBS>::from_slice(&all[1..]).is_flattened());
+
+        let b = B::<LIMBS>::from_slice(&[0, mid - 1, mid, last]);
+        let parts = b.partition(&[0..mid - 1, mid - 1..mid + 1, mid + 1..capacity]);
+        assert_eq!(parts[0], B::from_slice(&[0]));
+        assert_eq!(parts[1], B::from_slice(&[mid - 1, mid]));
+        assert_eq!(parts[2], B::from_slice(&[last]));
+        assert_eq!(
+            parts[1].minus(&B::from_slice(&[mid])),
+            B::from_slice(&[mid - 1])
+        );
+
+        let mut popped = b.clone();
+        assert_eq!(popped.pop(), Some(last));
+        assert_eq!(popped.pop(), Some(mid));
+        assert_eq!(popped.pop_lowest(), Some(0));
+        assert_eq!(popped.pop_lowest(), Some(mid - 1));
+        assert_eq!(popped.pop(), None);
+
+        assert!(B::<LIMBS>::from_slice(&[mid - 1]) < B::from_slice(&[mid]));
+        assert!(B::<LIMBS>::from_slice(&[0, mid]) < B::from_slice(&[last]));
+    }
+
+    #[test]
+    fn test_bitset_n() {
+        check_bitset_n::<1>();
+        check_bitset_n::<2>();
+        check_bitset_n::<4>();
+        assert_eq!(
+            BitsetN::<2>::from_slice(&[0, 63]).cmp(&BitsetN::from_slice(&[64])),
+            Bitset128::from_slice(&[0, 63]).cmp(&Bitset128::from_slice(&[64]))
+        );
+    }
 }
@@ -1,5 +1,5 @@
 use core::hash;
-use std::{cmp::Reverse, collections::HashMap, hash::Hash, mem, ops::Range};
+use std::{cmp::Reverse, collections::HashMap, hash::Hash, mem, ops::Range, sync::OnceLock};
 use union_find::{QuickUnionUf, UnionByRank, UnionFind};
 
 use crate::hypergraph::Set;
@@ -13,6 +13,9 @@ where
     nodes: Vec<usize>,
     node_structure_partitions: Vec<usize>,
     edge_structure_partitions: Vec<usize>,
+    canonicalization_converged: bool,
+    /// Lazily computed [`StructuredHypergraph::dual`], reset whenever hyperedges or nodes change.
+    dual: OnceLock<Vec<Vec<usize>>>,
 }
 
 impl<E> PartialEq for StructuredHypergraph<E>
@@ -90,8 +93,24 @@ where
             .collect()
     }
 
+    /// Returns true if canonical sorting reached a fixpoint when the hypergraph was built.
+    ///
+    /// If the sorter hit its iteration cap, equal games may not have equal
+    /// representations, so equality should not be trusted for caching.
+    pub fn canonicalization_converged(&self) -> bool {
+        self.canonicalization_converged
+    }
+
+    /// Replaces the node labels with their canonical indices `0..N`.
+    pub fn normalize_labels(&mut self) {
+        self.nodes = (0..self.nodes.len()).collect();
+    }
+
     /// Removes the given nodes and returns resulting hypergraph components.
-    pub fn minus(&self, nodes: E) -> Vec<Self> {
+    pub fn minus(&self, nodes: E) -> Vec<Self>
+    where
+        E: Ord,
+    {
         Self::from_hyperedges_with_nodes(
             self.hyperedges.iter().map(|e| e.minus(&nodes)).collect(),
             self.nodes.clone(),
@@ -99,7 +118,10 @@ where
     }
 
     /// Constructs hypergraphs from raw hyperedges.
-    pub fn from_hyperedges(hyperedges: Vec<E>) -> Vec<StructuredHypergraph<E>> {
+    pub fn from_hyperedges(hyperedges: Vec<E>) -> Vec<StructuredHypergraph<E>>
+    where
+        E: Ord,
+    {
         let max_node = hyperedges
             .iter()
             .flat_map(|e| e.iter())
@@ -117,21 +139,57 @@ where
     pub fn from_hyperedges_with_nodes(
         hyperedges: Vec<E>,
         nodes: Vec<usize>,
-    ) -> Vec<StructuredHypergraph<E>> {
+    ) -> Vec<StructuredHypergraph<E>>
+    where
+        E: Ord,
+    {
         let mut g = Self {
             hyperedges,
             edge_structure_partitions: Vec::new(),
             node_structure_partitions: Vec::new(),
             nodes,
+            canonicalization_converged: false,
+            dual: OnceLock::new(),
         };
         g.remove_redundant_hyperedges();
         g.get_parts()
     }
 
+    /// Constructs a single hypergraph without canonical ordering.
+    ///
+    /// Nodes are flattened and redundant hyperedges removed, but the result is
+    /// neither split into parts nor canonically sorted. Every node and hyperedge
+    /// is placed in its own structural partition.
+    pub fn from_hyperedges_unsorted(hyperedges: Vec<E>) -> Self {
+        let max_node = hyperedges
+            .iter()
+            .flat_map(|e| e.iter())
+            .max()
+            .map(|max| max + 1)
+            .unwrap_or_default();
+        let mut g = Self {
+            hyperedges,
+            edge_structure_partitions: Vec::new(),
+            node_structure_partitions: Vec::new(),
+            nodes: (0..max_node).collect(),
+            canonicalization_converged: false,
+            dual: OnceLock::new(),
+        };
+        g.remove_redundant_hyperedges();
+        g.edge_structure_partitions = (0..=g.hyperedges.len()).collect();
+        g.node_structure_partitions = (0..=g.nodes.len()).collect();
+        g
+    }
+
     /// Returns the dual hypergraph representation.
     ///
-    /// Each node is mapped to the list of incident hyperedges.
-    pub fn dual(&self) -> Vec<Vec<usize>> {
+    /// Each node is mapped to the list of incident hyperedges. The dual is computed
+    /// on first use and cached.
+    pub fn dual(&self) -> &[Vec<usize>] {
+        self.dual.get_or_init(|| self.compute_dual())
+    }
+
+    fn compute_dual(&self) -> Vec<Vec<usize>> {
         let mut dual = vec![Vec::new(); self.nodes.len()];
         for (i, edge) in self.hyperedges.iter().enumerate() {
             for node in edge.iter() {
@@ -141,12 +199,83 @@ where
         dual
     }
 
+    /// Returns the indices of the hyperedges containing `node`.
+    ///
+    /// This is a single row of [`StructuredHypergraph::dual`].
+    pub fn edges_of_node(&self, node: usize) -> Vec<usize> {
+        self.hyperedges
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.contains(&node))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Returns true if no hyperedge is a subset of another.
+    ///
+    /// This invariant is established by redundant hyperedge removal on construction.
+    pub fn has_no_redundant_edges(&self) -> bool {
+        self.hyperedges.iter().enumerate().all(|(i, e)| {
+            self.hyperedges
+                .iter()
+                .enumerate()
+                .all(|(j, f)| i == j || !e.is_subset(f))
+        })
+    }
+
+    /// Panics if the internal representation is inconsistent.
+    ///
+    /// Checks that:
+    /// - every hyperedge is non-empty and not a subset of another,
+    /// - hyperedges only contain nodes in `0..N` and every node is covered,
+    /// - both partition vectors are strictly increasing and bracket the full range.
+    #[cfg(debug_assertions)]
+    pub fn assert_invariants(&self)
+    where
+        E: Eq,
+    {
+        let mut all_nodes = E::default();
+        for (i, e) in self.hyperedges.iter().enumerate() {
+            assert!(!e.is_empty(), "hyperedge {i} is empty");
+            assert!(
+                e.iter().all(|n| n < self.nodes.len()),
+                "hyperedge {i} contains a node out of range"
+            );
+            all_nodes.union(e);
+        }
+        assert!(
+            self.has_no_redundant_edges(),
+            "a hyperedge is a subset of another"
+        );
+        assert_eq!(
+            all_nodes.len(),
+            self.nodes.len(),
+            "not every node is covered"
+        );
+        for (partitions, len) in [
+            (&self.node_structure_partitions, self.nodes.len()),
+            (&self.edge_structure_partitions, self.hyperedges.len()),
+        ] {
+            assert_eq!(partitions.first(), Some(&0), "partitions must start at 0");
+            assert_eq!(
+                partitions.last(),
+                Some(&len),
+                "partitions must end at {len}"
+            );
+            assert!(
+                partitions.windows(2).all(|w| w[0] < w[1]),
+                "partitions must be strictly increasing"
+            );
+        }
+    }
+
     /// Normalize node indices:
     /// - Maps arbitrary node labels to a compact range [0..N).
     /// - Updates both `hyperedges` and `nodes`.
     ///
     /// Assumes `nodes` is consistent with hyperedges.
     fn flatten_nodes(&mut self) {
+        self.dual = OnceLock::new();
         let mut all_nodes = E::default();
 
         // union of all nodes appearing in hyperedges
@@ -178,14 +307,63 @@ where
             }
         }
         self.hyperedges = new_edges;
+        self.dual = OnceLock::new();
         if prev_hyperedges_len != self.hyperedges.len() {
             // re-flatten after removal to ensure consistent node mapping
             self.flatten_nodes();
         }
     }
 
+    /// Returns true if the hyperedges form a single connected component.
+    ///
+    /// Grows the set of reached nodes from the first hyperedge until no hyperedge
+    /// overlaps it without being contained in it. This is cheaper than a full
+    /// union-find pass for the common case of a move that does not split the game.
+    pub fn is_connected(&self) -> bool {
+        let Some((first, rest)) = self.hyperedges.split_first() else {
+            return false;
+        };
+        let mut reached = E::default();
+        reached.union(first);
+        let mut pending: Vec<&E> = rest.iter().collect();
+        loop {
+            let before = pending.len();
+            pending.retain(|e| {
+                if e.intersects(&reached) {
+                    reached.union(e);
+                    false
+                } else {
+                    true
+                }
+            });
+            if pending.is_empty() {
+                return true;
+            }
+            if pending.len() == before {
+                return false;
+            }
+        }
+    }
+
     /// Returns disconnected parts of the hypergraph as separate StructuredHypergraphs.
-    fn get_parts(mut self) -> Vec<StructuredHypergraph<E>> {
+    ///
+    /// Parts are sorted by [`Ord`], ties broken by the node labels, so the order is
+    /// deterministic. Connected hypergraphs skip the union-find pass of [`Self::split_parts`].
+    fn get_parts(self) -> Vec<StructuredHypergraph<E>>
+    where
+        E: Ord,
+    {
+        if self.is_connected() {
+            return vec![StructuralHypergraphSorter::new(self).sort()];
+        }
+        self.split_parts()
+    }
+
+    /// Splits the hypergraph into its connected components using union-find.
+    fn split_parts(mut self) -> Vec<StructuredHypergraph<E>>
+    where
+        E: Ord,
+    {
         let mut uf: QuickUnionUf<UnionByRank> = QuickUnionUf::new(self.nodes.len());
 
         // Union all nodes in each hyperedge
@@ -228,16 +406,21 @@ where
                 nodes: self.nodes.clone(),
                 node_structure_partitions: vec![],
                 edge_structure_partitions: vec![],
+                canonicalization_converged: false,
+                dual: OnceLock::new(),
             };
             part.flatten_nodes();
             parts.push(StructuralHypergraphSorter::new(part).sort());
         }
+        // buckets iterate in arbitrary order, sort so the result is deterministic
+        parts.sort_by(|a, b| a.cmp(b).then_with(|| a.nodes.cmp(&b.nodes)));
         parts
     }
 
     /// Applies a permutation to reorder edges.
     /// Assumes map contains a valid permutation of edge indices.
     fn apply_edge_map(&mut self, map: &[usize]) {
+        self.dual = OnceLock::new();
         let mut old_edges: Vec<Option<E>> =
             self.hyperedges.drain(..).map(|node| Some(node)).collect();
         self.hyperedges = map
@@ -254,6 +437,7 @@ where
     /// Also updates hyperedges to reflect new node indices.
     /// Assumes `map` is a valid reordering of [0..nodes).
     fn apply_node_map(&mut self, map: &[usize]) {
+        self.dual = OnceLock::new();
         for edge in self.hyperedges.iter_mut() {
             edge.apply_node_map(map);
         }
@@ -325,7 +509,6 @@ where
     node_keys: Vec<Vec<usize>>,
     edge_keys: Vec<Vec<usize>>,
 
-    dual: Vec<Vec<usize>>,
     hypergraph: StructuredHypergraph<E>,
 }
 
@@ -342,7 +525,6 @@ where
     /// - Builds initial node and edge keys based on sizes of incident edges/nodes.
     pub fn new(hypergraph: StructuredHypergraph<E>) -> Self {
         let buffsize = hypergraph.nodes.len().max(hypergraph.hyperedges.len());
-        let dual = hypergraph.dual();
         Self {
             node_map: (0..hypergraph.nodes.len()).collect(),
             edge_map: (0..hypergraph.hyperedges.len()).collect(),
@@ -350,9 +532,8 @@ where
             temp_buffer: Vec::with_capacity(buffsize),
             key_map_buffer: Vec::with_capacity(buffsize),
 
-            node_keys: Self::get_initial_keys(dual.iter().map(|n| n.len())),
+            node_keys: Self::get_initial_keys(hypergraph.dual().iter().map(|n| n.len())),
             edge_keys: Self::get_initial_keys(hypergraph.hyperedges.iter().map(|n| n.len())),
-            dual,
             hypergraph,
         }
     }
@@ -382,7 +563,7 @@ where
         self.sort_nodes();
 
         self.build_structural_eq_classes();
-        self.sort_canonically();
+        self.hypergraph.canonicalization_converged = self.sort_canonically();
 
         self.hypergraph.apply_edge_map(&self.edge_map);
         self.hypergraph.apply_node_map(&self.node_map);
@@ -426,7 +607,8 @@ where
             }
         }
     }
-    fn sort_canonically(&mut self) {
+    /// Returns false if no fixpoint was reached within MAX_ITER iterations.
+    fn sort_canonically(&mut self) -> bool {
         for _ in 0..Self::MAX_ITER {
             Self::fill_inv_permutation(&mut self.key_map_buffer, &self.edge_map);
             for k in self.key_map_buffer.iter_mut() {
@@ -448,9 +630,10 @@ where
             self.sort_edges();
             let edge_perm_unchanged = self.temp_buffer == self.edge_map;
             if edge_perm_unchanged && node_perm_unchanged {
-                return;
+                return true;
             }
         }
+        false
     }
 
     fn build_edge_keys(&mut self) {
@@ -461,7 +644,7 @@ where
         }
     }
     fn build_node_keys(&mut self) {
-        for (i, n) in self.dual.iter().enumerate() {
+        for (i, n) in self.hypergraph.dual().iter().enumerate() {
             self.node_keys[i].clear();
             self.node_keys[i].extend(n.iter().map(|e| self.key_map_buffer[*e]));
             self.node_keys[i].sort_unstable();
@@ -545,7 +728,7 @@ where
 }
 #[cfg(test)]
 mod tests {
-    use crate::hypergraph::Bitset128;
+    use crate::hypergraph::{Bitset128, DynamicBitset};
 
     use super::*;
 
@@ -594,6 +777,8 @@ mod tests {
             nodes: (0..=6).collect(),
             node_structure_partitions: vec![],
             edge_structure_partitions: vec![],
+            canonicalization_converged: false,
+            dual: OnceLock::new(),
         };
         g.flatten_nodes();
         // node indices should now be 0,1,2
@@ -618,6 +803,95 @@ mod tests {
         assert!(sizes.contains(&3)); // nodes 0,1,2
         assert!(sizes.contains(&2)); // nodes 3,4
     }
+    #[test]
+    fn test_dynamic_bitset_beyond_128_nodes() {
+        let edges: Vec<DynamicBitset> = (0..199)
+            .map(|i| DynamicBitset::from_slice(&[i, i + 1]))
+            .collect();
+        let parts = StructuredHypergraph::from_hyperedges(edges);
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].nr_nodes(), 200);
+        assert_eq!(parts[0].hyperedges().len(), 199);
+
+        let index = parts[0].nodes().iter().position(|&n| n == 150).unwrap();
+        let split = parts[0].minus(DynamicBitset::from_slice(&[index]));
+        let mut sizes: Vec<usize> = split.iter().map(|p| p.nr_nodes()).collect();
+        sizes.sort();
+        assert_eq!(sizes, vec![49, 150]);
+    }
+
+    #[test]
+    fn test_connectivity_fast_path_matches_union_find() {
+        let edges: Vec<Bitset128> = (0..11)
+            .map(|i| Bitset128::from_slice(&[i, i + 1]))
+            .collect();
+        let kayles = StructuredHypergraph::from_hyperedges(edges).remove(0);
+        for removed in 0..kayles.nr_nodes() {
+            let mut g = StructuredHypergraph {
+                hyperedges: kayles
+                    .hyperedges()
+                    .iter()
+                    .map(|e| e.minus(&Bitset128::from_slice(&[removed])))
+                    .collect(),
+                nodes: kayles.nodes().to_vec(),
+                node_structure_partitions: vec![],
+                edge_structure_partitions: vec![],
+                canonicalization_converged: false,
+                dual: OnceLock::new(),
+            };
+            g.remove_redundant_hyperedges();
+            let mut incremental = g.clone().get_parts();
+            let mut full = g.split_parts();
+            incremental.sort();
+            full.sort();
+            assert_eq!(incremental, full);
+            assert_eq!(
+                incremental.len(),
+                kayles.minus(Bitset128::from_slice(&[removed])).len()
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_connected() {
+        let connected = StructuredHypergraph::from_hyperedges_unsorted(vec![
+            Bitset128::from_slice(&[2, 3]),
+            Bitset128::from_slice(&[0, 1]),
+            Bitset128::from_slice(&[1, 2]),
+        ]);
+        assert!(connected.is_connected());
+        let split = StructuredHypergraph::from_hyperedges_unsorted(vec![
+            Bitset128::from_slice(&[0, 1]),
+            Bitset128::from_slice(&[2, 3]),
+        ]);
+        assert!(!split.is_connected());
+    }
+
+    #[test]
+    fn test_get_parts_order_is_deterministic() {
+        let heap = [Bitset128::from_slice(&[0, 1])];
+        let kayles = [
+            Bitset128::from_slice(&[2, 3]),
+            Bitset128::from_slice(&[3, 4]),
+        ];
+        let expected =
+            StructuredHypergraph::from_hyperedges([heap.to_vec(), kayles.to_vec()].concat());
+        for _ in 0..20 {
+            let parts =
+                StructuredHypergraph::from_hyperedges([kayles.to_vec(), heap.to_vec()].concat());
+            assert_eq!(parts, expected);
+            assert_eq!(
+                parts.iter().map(|p| p.nodes().to_vec()).collect::<Vec<_>>(),
+                expected
+                    .iter()
+                    .map(|p| p.nodes().to_vec())
+                    .collect::<Vec<_>>()
+            );
+        }
+        assert_eq!(expected[0].nr_nodes(), 2);
+        assert_eq!(expected[1].nr_nodes(), 3);
+    }
+
     #[test]
     fn test_canonization() {
         let edges = vec![
@@ -657,6 +931,39 @@ mod tests {
         assert_eq!(dual[2], vec![0, 1]);
     }
 
+    #[test]
+    fn test_cached_dual_matches_uncached() {
+        let edges = vec![
+            Bitset128::from_slice(&[0, 1, 2]),
+            Bitset128::from_slice(&[2, 3]),
+            Bitset128::from_slice(&[3, 4, 5]),
+        ];
+        let g = StructuredHypergraph::from_hyperedges(edges).remove(0);
+        assert_eq!(g.dual(), g.compute_dual());
+        assert_eq!(g.dual(), g.compute_dual());
+
+        for part in g.minus(Bitset128::from_slice(&[3])) {
+            assert_eq!(part.dual(), part.compute_dual());
+        }
+
+        let mut permuted = g.clone();
+        permuted.apply_node_map(&[5, 4, 3, 2, 1, 0]);
+        assert_eq!(permuted.dual(), permuted.compute_dual());
+        assert_ne!(permuted.dual(), g.dual());
+    }
+
+    #[test]
+    fn test_edges_of_node() {
+        let edges = vec![
+            Bitset128::from_slice(&[0, 1]),
+            Bitset128::from_slice(&[1, 2]),
+        ];
+        let g = &StructuredHypergraph::from_hyperedges(edges)[0];
+        for (node, edges) in g.dual().iter().enumerate() {
+            assert_eq!(&g.edges_of_node(node), edges);
+        }
+    }
+
     #[test]
     fn test_apply_node_map() {
         let edges = vec![
@@ -668,7 +975,7 @@ mod tests {
         let all_nodes: Vec<usize> = g.hyperedges().iter().flat_map(|e| e.iter()).collect();
         assert_eq!(
             all_nodes.len(),
-            g.hyperedges().iter().map(|e| e.len()).sum()
+            g.hyperedges().iter().map(|e| e.len()).sum::<usize>()
         );
         assert!(all_nodes.iter().all(|&n| n < g.nr_nodes()));
     }
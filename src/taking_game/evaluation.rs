@@ -0,0 +1,530 @@
+use std::collections::HashMap;
+
+use evaluator::{Evaluator, Impartial};
+use rayon::prelude::*;
+
+use super::TakingGame;
+use crate::hypergraph::{Bitset128, Set};
+
+/// Aggregated statistics over the moves of a position, see [`TakingGame::classify_moves`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MoveStats {
+    /// The number of moves.
+    pub total: usize,
+    /// Moves leaving at most one component.
+    pub connected: usize,
+    /// Moves splitting the game into several components.
+    pub splitting: usize,
+    /// Moves reaching a P-position (nimber 0), i.e. winning moves.
+    pub to_p_position: usize,
+    /// Moves reaching the highest nimber among all moves.
+    pub to_highest_nimber: usize,
+}
+
+/// Search effort spent evaluating a game, see [`TakingGame::get_nimber_with_stats`].
+///
+/// Only the growth of the evaluator's cache is observable from outside the
+/// evaluator; its recursion depth is not.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchStats {
+    /// The number of positions added to the evaluator's cache during the call.
+    pub new_cache_entries: usize,
+}
+
+/// The outcome of [`TakingGame::get_nimber_bounded`].
+///
+/// There is no `Cancelled` variant: the [`Evaluator`] offers no hook to interrupt
+/// a running evaluation, and a position budget already makes every partial
+/// result reproducible, which is what wall-clock cancellation could not.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NimberResult {
+    /// The nimber was determined within the budget.
+    Known(usize),
+    /// The budget of positions was exhausted before the nimber was determined.
+    Unknown,
+}
+
+/// The canonical hyperedges and subtraction sets of a position.
+type PositionKey = (Vec<Bitset128>, Vec<Option<Vec<usize>>>);
+
+/// Evaluates `game` like [`Evaluator::get_nimber`], caching at most `budget` positions.
+///
+/// Positions are expanded with [`Impartial::get_split_moves`] and the
+/// [`Impartial::get_max_nimber`] shortcut for symmetric games, and keyed by their
+/// canonical hyperedges and subtraction sets, the parts of equality the evaluator
+/// keys them by. Returns
+/// `None` once the cache is full and another position would be needed.
+fn bounded_nimber(
+    game: &TakingGame,
+    cache: &mut HashMap<PositionKey, usize>,
+    budget: usize,
+) -> Option<usize> {
+    let key = (
+        game.graph.hyperedges().to_vec(),
+        game.edge_subtraction_sets(),
+    );
+    if let Some(nimber) = cache.get(&key) {
+        return Some(*nimber);
+    }
+    if cache.len() >= budget {
+        return None;
+    }
+    let nimber = if game.get_max_nimber() == Some(0) {
+        0
+    } else {
+        let mut seen = vec![];
+        for parts in game.get_split_moves() {
+            let mut nimber = 0;
+            for part in &parts {
+                nimber ^= bounded_nimber(part, cache, budget)?;
+            }
+            seen.push(nimber);
+        }
+        mex(seen)
+    };
+    if cache.len() >= budget {
+        return None;
+    }
+    cache.insert(key, nimber);
+    Some(nimber)
+}
+
+/// Returns the minimum excludant of `values`, the smallest natural number not among them.
+///
+/// This is the nimber of a position whose options have the nimbers `values`.
+pub fn mex(values: impl IntoIterator<Item = usize>) -> usize {
+    let mut values: Vec<usize> = values.into_iter().collect();
+    values.sort_unstable();
+    values.dedup();
+    values
+        .iter()
+        .enumerate()
+        .find(|(i, v)| i != *v)
+        .map_or(values.len(), |(i, _)| i)
+}
+
+/// Detects eventual periodicity of a nimber sequence, e.g. of a [`Builder`] family.
+///
+/// Returns `(preperiod, period)` such that `nimbers[n] == nimbers[n + period]`
+/// for all `n >= preperiod`, confirmed over at least `preperiod + 2 * period`
+/// values. Among all confirmed candidates the smallest preperiod is chosen, ties
+/// broken by the smallest period. Returns `None` if no period is confirmed.
+///
+/// [`Builder`]: crate::builder::Builder
+pub fn detect_period(nimbers: &[usize]) -> Option<(usize, usize)> {
+    (1..=nimbers.len() / 2)
+        .filter_map(|period| {
+            let preperiod = (0..nimbers.len() - period)
+                .rev()
+                .find(|&n| nimbers[n] != nimbers[n + period])
+                .map_or(0, |n| n + 1);
+            (preperiod + 2 * period <= nimbers.len()).then_some((preperiod, period))
+        })
+        .min()
+}
+
+impl TakingGame {
+    /// Returns the value of the game in CGT notation.
+    ///
+    /// P-positions render as `0`, nimber 1 as `*` and nimber `k` as `*k`.
+    /// Returns `?` if the evaluator could not determine the nimber.
+    pub fn game_value_string(&self, eval: &Evaluator<TakingGame>) -> String {
+        match eval.get_nimber(self) {
+            Some(0) => "0".to_string(),
+            Some(1) => "*".to_string(),
+            Some(k) => format!("*{k}"),
+            None => "?".to_string(),
+        }
+    }
+
+    /// Returns the size `k` of the Nim heap this position plays exactly like.
+    ///
+    /// This is the nimber, named for teaching output; see [`Builder::target_nimber`]
+    /// for the equivalent heap itself.
+    ///
+    /// [`Builder::target_nimber`]: crate::builder::Builder::target_nimber
+    pub fn equivalent_heap(&self, eval: &Evaluator<TakingGame>) -> Option<usize> {
+        eval.get_nimber(self)
+    }
+
+    /// Returns the sorted distinct nimbers of the positions reachable in one move.
+    ///
+    /// The nimber of a move splitting the game is the XOR over its components.
+    /// The nimber of the game itself is the mex of the returned values, which
+    /// helps to understand why it came out as it did. Returns `None` if any
+    /// resulting position could not be evaluated.
+    pub fn child_nimbers(&self, eval: &Evaluator<TakingGame>) -> Option<Vec<usize>> {
+        let mut nimbers: Vec<usize> = self
+            .get_split_moves()
+            .iter()
+            .map(|parts| eval.get_nimber_by_parts(parts))
+            .collect::<Option<_>>()?;
+        nimbers.sort_unstable();
+        nimbers.dedup();
+        Some(nimbers)
+    }
+
+    /// Classifies all moves of the game in a single pass over its split moves.
+    ///
+    /// `connected + splitting == total` always holds.
+    /// Returns `None` if any resulting position could not be evaluated.
+    pub fn classify_moves(&self, eval: &Evaluator<TakingGame>) -> Option<MoveStats> {
+        let moves = self.get_split_moves();
+        let nimbers: Vec<usize> = moves
+            .iter()
+            .map(|parts| eval.get_nimber_by_parts(parts))
+            .collect::<Option<_>>()?;
+        let highest = nimbers.iter().max().copied();
+        Some(MoveStats {
+            total: moves.len(),
+            connected: moves.iter().filter(|parts| parts.len() <= 1).count(),
+            splitting: moves.iter().filter(|parts| parts.len() > 1).count(),
+            to_p_position: nimbers.iter().filter(|&&n| n == 0).count(),
+            to_highest_nimber: nimbers.iter().filter(|&&n| Some(n) == highest).count(),
+        })
+    }
+
+    /// Returns the original labels of the nodes to remove to reach a P-position.
+    ///
+    /// This is a winning move for the player to move. Returns `None` if the game
+    /// is already a P-position, or if it could not be evaluated. Moves are built
+    /// lazily, so the search stops at the first winning move.
+    pub fn best_move(&self, eval: &Evaluator<TakingGame>) -> Option<Vec<usize>> {
+        if eval.get_nimber(self)? == 0 {
+            return None;
+        }
+        let removed = self.masks_iter().find(|mask| {
+            eval.get_nimber_by_parts(&self.with_nodes_from_set_removed(mask.clone())) == Some(0)
+        })?;
+        Some(removed.iter().map(|n| self.nodes()[n]).collect())
+    }
+
+    /// Evaluates the game while exploring at most `max_positions` distinct positions.
+    ///
+    /// The budget is checked by a search over the same moves as the evaluator's.
+    /// Whether the nimber is known therefore only depends on the game and the
+    /// budget, never on timing or on what `eval` has cached before. Once the
+    /// search succeeds, the game is evaluated by `eval` as well, so the result is
+    /// shared with later [`Evaluator::get_nimber`] calls. Returns
+    /// [`NimberResult::Unknown`] if the budget is exhausted.
+    pub fn get_nimber_bounded(
+        &self,
+        eval: &Evaluator<TakingGame>,
+        max_positions: usize,
+    ) -> NimberResult {
+        match bounded_nimber(self, &mut HashMap::new(), max_positions) {
+            Some(_) => eval
+                .get_nimber(self)
+                .map_or(NimberResult::Unknown, NimberResult::Known),
+            None => NimberResult::Unknown,
+        }
+    }
+
+    /// Evaluates the game and reports how much the evaluator's cache grew.
+    ///
+    /// Positions cached by earlier calls are not counted again. The count is the
+    /// difference of two cache sizes, so it is only exact if no other thread uses
+    /// `eval` at the same time; concurrent evaluations are counted as well.
+    pub fn get_nimber_with_stats(
+        &self,
+        eval: &Evaluator<TakingGame>,
+    ) -> (Option<usize>, SearchStats) {
+        let before = eval.get_cache_size();
+        let nimber = eval.get_nimber(self);
+        let stats = SearchStats {
+            new_cache_entries: eval.get_cache_size().saturating_sub(before),
+        };
+        (nimber, stats)
+    }
+
+    /// Pairs each component of a built game with its nimber.
+    ///
+    /// The nimber of the whole game is the XOR of the returned nimbers.
+    /// Returns `None` if any component could not be evaluated.
+    pub fn component_nimbers(
+        parts: &[TakingGame],
+        eval: &Evaluator<TakingGame>,
+    ) -> Option<Vec<(TakingGame, usize)>> {
+        parts
+            .iter()
+            .map(|p| eval.get_nimber(p).map(|n| (p.clone(), n)))
+            .collect()
+    }
+
+    /// Returns the nimber of the disjoint sum of separately built games.
+    ///
+    /// Each game is evaluated on its own with the shared cache of `eval` and the
+    /// results are XORed, so the games never get merged into one hypergraph
+    /// where equal labels would connect them. Returns `None` if any game could
+    /// not be evaluated.
+    pub fn nimber_of_sum(games: &[&TakingGame], eval: &Evaluator<TakingGame>) -> Option<usize> {
+        games
+            .iter()
+            .try_fold(0, |nimber, game| Some(nimber ^ eval.get_nimber(game)?))
+    }
+
+    /// Buckets games by their nimber, returning the indices into `games` per nimber.
+    ///
+    /// Games in one bucket are interchangeable in any sum even if they are not
+    /// isomorphic. Games that could not be evaluated are left out.
+    pub fn group_by_nimber(
+        games: &[TakingGame],
+        eval: &Evaluator<TakingGame>,
+    ) -> HashMap<usize, Vec<usize>> {
+        let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (i, game) in games.iter().enumerate() {
+            if let Some(nimber) = eval.get_nimber(game) {
+                groups.entry(nimber).or_default().push(i);
+            }
+        }
+        groups
+    }
+
+    /// Evaluates the components of a built game in parallel and returns the XOR of their nimbers.
+    ///
+    /// All threads share `eval`, so its cache and cancellation apply as for
+    /// [`Evaluator::get_nimber_by_parts`]. Returns `None` if any component could not
+    /// be evaluated. Plain Nim, see [`TakingGame::as_nim_heaps`], is answered without
+    /// a search.
+    pub fn parallel_nimber(parts: &[TakingGame], eval: &Evaluator<TakingGame>) -> Option<usize> {
+        if let Some(heaps) = Self::as_nim_heaps(parts) {
+            return Some(heaps.iter().fold(0, |acc, h| acc ^ h));
+        }
+        parts
+            .par_iter()
+            .map(|p| eval.get_nimber(p))
+            .try_reduce(|| 0, |a, b| Some(a ^ b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use evaluator::{Evaluator, Impartial};
+
+    use super::{NimberResult, detect_period, mex};
+    use crate::{builder::Builder, taking_game::TakingGame};
+
+    #[test]
+    fn test_mex() {
+        assert_eq!(mex([]), 0);
+        assert_eq!(mex([1, 2]), 0);
+        assert_eq!(mex([2, 0, 1, 0]), 3);
+        assert_eq!(mex([0, 1, 3]), 2);
+    }
+
+    #[test]
+    fn test_detect_period() {
+        // Kayles is the octal game 0.77, whose nimbers have period 12 after 71 values.
+        let mut kayles: Vec<usize> = vec![];
+        for n in 0..100 {
+            let options: Vec<usize> = (1..=2)
+                .filter(|&taken| taken <= n)
+                .flat_map(|taken| (0..=n - taken).map(move |a| (a, n - taken - a)))
+                .map(|(a, b)| kayles[a] ^ kayles[b])
+                .collect();
+            kayles.push(mex(options));
+        }
+        let eval = Evaluator::new();
+        for (n, nimber) in kayles.iter().enumerate().take(9).skip(1) {
+            let g = Builder::kayles(n).build_one().unwrap();
+            assert_eq!(eval.get_nimber(&g), Some(*nimber));
+        }
+        assert_eq!(detect_period(&kayles), Some((71, 12)));
+        assert_eq!(detect_period(&kayles[..94]), None);
+
+        assert_eq!(detect_period(&[0, 1, 2, 0, 1, 2]), Some((0, 3)));
+        assert_eq!(detect_period(&[5, 0, 1, 0, 1]), Some((1, 2)));
+        assert_eq!(detect_period(&[1, 2, 3]), None);
+        assert_eq!(detect_period(&[]), None);
+    }
+
+    #[test]
+    fn test_game_value_string() {
+        let eval = Evaluator::new();
+        let heap = Builder::heap(3).build_one().unwrap();
+        assert_eq!(heap.game_value_string(&eval), "*3");
+        let unit = Builder::unit().build_one().unwrap();
+        assert_eq!(unit.game_value_string(&eval), "*");
+        let square = Builder::rect(2, 2).build_one().unwrap();
+        assert_eq!(square.game_value_string(&eval), "0");
+    }
+
+    #[test]
+    fn test_equivalent_heap() {
+        let eval = Evaluator::new();
+        let kayles = Builder::kayles(3).build_one().unwrap();
+        assert_eq!(kayles.equivalent_heap(&eval), Some(3));
+        let heap = Builder::target_nimber(3).build_one().unwrap();
+        assert_eq!(eval.get_nimber(&heap), Some(3));
+    }
+
+    #[test]
+    fn test_child_nimbers() {
+        let eval = Evaluator::new();
+        let heap = Builder::heap(3).build_one().unwrap();
+        assert_eq!(heap.child_nimbers(&eval), Some(vec![0, 1, 2]));
+        assert_eq!(eval.get_nimber(&heap), Some(3));
+
+        let kayles = Builder::kayles(5).build_one().unwrap();
+        let children = kayles.child_nimbers(&eval).unwrap();
+        assert_eq!(eval.get_nimber(&kayles), Some(mex(children)));
+    }
+
+    #[test]
+    fn test_classify_moves() {
+        let eval = Evaluator::new();
+        let g = Builder::kayles(5).build_one().unwrap();
+        let stats = g.classify_moves(&eval).unwrap();
+        assert_eq!(stats.total, g.get_split_moves().len());
+        assert_eq!(stats.connected + stats.splitting, stats.total);
+        assert!(stats.splitting > 0);
+        // kayles(5) has nimber 4, so there is a winning move
+        assert!(stats.to_p_position > 0);
+        assert!(stats.to_highest_nimber > 0);
+    }
+
+    #[test]
+    fn test_nimber_of_sum() {
+        let eval = Evaluator::new();
+        let a = Builder::heap(3).build_one().unwrap();
+        let b = Builder::heap(5).build_one().unwrap();
+        let xor = eval.get_nimber(&a).unwrap() ^ eval.get_nimber(&b).unwrap();
+        assert_eq!(TakingGame::nimber_of_sum(&[&a, &b], &eval), Some(xor));
+        assert_eq!(xor, 3 ^ 5);
+
+        // Both games use labels starting at 0; merging them would connect them.
+        let kayles = Builder::kayles(4).build_one().unwrap();
+        assert_eq!(
+            TakingGame::nimber_of_sum(&[&kayles, &kayles], &eval),
+            Some(0)
+        );
+        assert_eq!(TakingGame::nimber_of_sum(&[], &eval), Some(0));
+    }
+
+    #[test]
+    fn test_group_by_nimber() {
+        let eval = Evaluator::new();
+        let games: Vec<TakingGame> = [
+            Builder::rect(2, 2),
+            Builder::heap(3),
+            Builder::rect(4, 4),
+            Builder::kayles(3),
+        ]
+        .into_iter()
+        .map(|b| b.build_one().unwrap())
+        .collect();
+        let groups = TakingGame::group_by_nimber(&games, &eval);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[&0], vec![0, 2]);
+        assert_eq!(groups[&3], vec![1, 3]);
+        assert!(!games[1].is_isomorphic_to(&games[3]));
+    }
+
+    #[test]
+    fn test_get_nimber_bounded() {
+        let eval = Evaluator::new();
+        let heap = Builder::heap(3).build_one().unwrap();
+        assert_eq!(heap.get_nimber_bounded(&eval, 3), NimberResult::Known(3));
+        assert_eq!(
+            heap.get_nimber_bounded(&Evaluator::new(), 2),
+            NimberResult::Unknown
+        );
+        // The budget does not depend on what the evaluator has cached.
+        assert_eq!(heap.get_nimber_bounded(&eval, 2), NimberResult::Unknown);
+
+        let kayles = Builder::kayles(7).build_one().unwrap();
+        let size = eval.get_cache_size();
+        assert_eq!(
+            kayles.get_nimber_bounded(&eval, 1000),
+            NimberResult::Known(2)
+        );
+        assert!(eval.get_cache_size() > size);
+
+        let symmetric = Builder::rect(4, 4).build_one().unwrap();
+        assert_eq!(
+            symmetric.get_nimber_bounded(&eval, 1),
+            NimberResult::Known(0)
+        );
+
+        let large = Builder::rect(6, 7).build_one().unwrap();
+        assert_eq!(large.get_nimber_bounded(&eval, 10), NimberResult::Unknown);
+        assert_eq!(large.get_nimber_bounded(&eval, 10), NimberResult::Unknown);
+    }
+
+    #[test]
+    fn test_parallel_nimber() {
+        let parts = Builder::heap(3)
+            .sum(Builder::kayles(5))
+            .sum(Builder::heap(6))
+            .sum(Builder::kayles(7))
+            .sum(Builder::heap(2))
+            .build();
+        let sequential = Evaluator::new().get_nimber_by_parts(&parts);
+        assert!(sequential.is_some());
+        assert_eq!(
+            TakingGame::parallel_nimber(&parts, &Evaluator::new()),
+            sequential
+        );
+        assert_eq!(TakingGame::parallel_nimber(&[], &Evaluator::new()), Some(0));
+
+        let eval = Evaluator::new();
+        let nim = Builder::heap(3).sum(Builder::heap(5)).build();
+        assert_eq!(TakingGame::parallel_nimber(&nim, &eval), Some(3 ^ 5));
+        assert_eq!(eval.get_cache_size(), 0);
+    }
+
+    #[test]
+    fn test_best_move() {
+        let eval = Evaluator::new();
+        let heap = Builder::heap(3).build_one().unwrap();
+        let removed = heap.best_move(&eval).unwrap();
+        assert_eq!(removed, heap.nodes().to_vec());
+
+        let square = Builder::rect(2, 2).build_one().unwrap();
+        assert!(square.best_move(&eval).is_none());
+
+        let kayles = Builder::kayles(5).build_one().unwrap();
+        let removed = kayles.best_move(&eval).unwrap();
+        let parts = kayles.with_nodes_removed(&removed);
+        assert_eq!(eval.get_nimber_by_parts(&parts), Some(0));
+
+        let sparse = Builder::from_labeled_hyperedges(vec![vec![200, 300, 400]])
+            .build_one()
+            .unwrap();
+        let mut removed = sparse.best_move(&eval).unwrap();
+        removed.sort();
+        assert_eq!(removed, vec![200, 300, 400]);
+    }
+
+    #[test]
+    fn test_get_nimber_with_stats() {
+        let eval = Evaluator::new();
+        let heap = Builder::heap(3).build_one().unwrap();
+        let (nimber, stats) = heap.get_nimber_with_stats(&eval);
+        assert_eq!(nimber, Some(3));
+        assert!(stats.new_cache_entries <= 10);
+
+        let (nimber, stats) = heap.get_nimber_with_stats(&eval);
+        assert_eq!(nimber, Some(3));
+        assert_eq!(stats.new_cache_entries, 0);
+    }
+
+    #[test]
+    fn test_component_nimbers() {
+        let eval = Evaluator::new();
+        let parts = Builder::heap(2)
+            .sum(Builder::heap(3))
+            .sum(Builder::heap(5))
+            .build();
+        let mut nimbers: Vec<usize> = TakingGame::component_nimbers(&parts, &eval)
+            .unwrap()
+            .into_iter()
+            .map(|(part, nimber)| {
+                assert_eq!(part.nr_nodes(), nimber);
+                nimber
+            })
+            .collect();
+        nimbers.sort();
+        assert_eq!(nimbers, vec![2, 3, 5]);
+    }
+}
@@ -0,0 +1,393 @@
+use std::fmt::Display;
+use std::io::{self, Write};
+
+use crate::builder::Builder;
+use crate::hypergraph::Set;
+
+use super::TakingGame;
+
+/// Why a game cannot be shown by [`TakingGame::display_as_grid`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    /// `width * height` differs from the number of nodes.
+    NodeCount {
+        width: usize,
+        height: usize,
+        nodes: usize,
+    },
+    /// The hyperedges are not the rows and columns of the grid.
+    NotARect { width: usize, height: usize },
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::NodeCount {
+                width,
+                height,
+                nodes,
+            } => write!(
+                f,
+                "a {width}x{height} grid does not fit a game of {nodes} nodes"
+            ),
+            GridError::NotARect { width, height } => {
+                write!(f, "the game is not a {width}x{height} rect")
+            }
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+/// Appends `value` to `bytes` as an unsigned LEB128 varint.
+fn push_varint(bytes: &mut Vec<u8>, mut value: usize) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes.push(byte);
+            return;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+impl TakingGame {
+    /// Writes the game in the line-based format read by [`Builder::from_dimacs_like`].
+    ///
+    /// The first line holds the node count (one past the largest label), every
+    /// following line one hyperedge as space-separated original node labels.
+    ///
+    /// [`Builder::from_dimacs_like`]: crate::builder::Builder::from_dimacs_like
+    pub fn write_hypergraph(&self, mut writer: impl Write) -> io::Result<()> {
+        let node_count = self.nodes().iter().max().map_or(0, |max| max + 1);
+        writeln!(writer, "{node_count}")?;
+        for edge in self.labeled_hyperedges() {
+            let labels: Vec<String> = edge.iter().map(|n| n.to_string()).collect();
+            writeln!(writer, "{}", labels.join(" "))?;
+        }
+        Ok(())
+    }
+
+    /// Encodes the game in graph6 format if every hyperedge has exactly two nodes.
+    ///
+    /// Nodes are encoded by their canonical index. Returns `None` for games that
+    /// are not simple graphs.
+    pub fn to_graph6(&self) -> Option<String> {
+        let edges = self.graph.hyperedges();
+        if edges.iter().any(|e| e.len() != 2) {
+            return None;
+        }
+        let n = self.nr_nodes();
+        let mut bytes = Vec::new();
+        if n < 63 {
+            bytes.push(n as u8 + 63);
+        } else {
+            bytes.push(126);
+            for shift in [12, 6, 0] {
+                bytes.push(((n >> shift) & 0x3f) as u8 + 63);
+            }
+        }
+        let mut bits = Vec::with_capacity(n * n.saturating_sub(1) / 2);
+        for j in 1..n {
+            for i in 0..j {
+                bits.push(edges.iter().any(|e| e.contains(&i) && e.contains(&j)));
+            }
+        }
+        for chunk in bits.chunks(6) {
+            let mut byte = 0u8;
+            for (k, &bit) in chunk.iter().enumerate() {
+                if bit {
+                    byte |= 1 << (5 - k);
+                }
+            }
+            bytes.push(byte + 63);
+        }
+        String::from_utf8(bytes).ok()
+    }
+
+    /// Renders the game as an undirected Graphviz DOT graph.
+    ///
+    /// Every node is a vertex labelled with its original label. Hyperedges of two
+    /// nodes become edges, larger hyperedges a small square net vertex connected to
+    /// each member, and single-node hyperedges are omitted.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("graph {\n");
+        for (i, label) in self.nodes().iter().enumerate() {
+            dot.push_str(&format!("  n{i} [label=\"{label}\"];\n"));
+        }
+        for (k, edge) in self.graph.hyperedges().iter().enumerate() {
+            let members: Vec<usize> = edge.iter().collect();
+            match members.as_slice() {
+                [a, b] => dot.push_str(&format!("  n{a} -- n{b};\n")),
+                [_, _, _, ..] => {
+                    dot.push_str(&format!(
+                        "  e{k} [shape=square, label=\"\", width=0.1, height=0.1];\n"
+                    ));
+                    for n in members {
+                        dot.push_str(&format!("  e{k} -- n{n};\n"));
+                    }
+                }
+                _ => {}
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Lays out the original node labels of a rectangular game as a `width` by `height` grid.
+    ///
+    /// Labels are placed in ascending order row by row, matching the numbering of
+    /// [`Builder::rect`], and right-aligned to a common width. Returns an error if
+    /// `width * height` differs from the number of nodes, or if the hyperedges are
+    /// not the rows and columns of [`Builder::rect`] under that numbering.
+    pub fn display_as_grid(&self, width: usize, height: usize) -> Result<String, GridError> {
+        if width * height != self.nr_nodes() {
+            return Err(GridError::NodeCount {
+                width,
+                height,
+                nodes: self.nr_nodes(),
+            });
+        }
+        let mut labels = self.nodes().to_vec();
+        labels.sort();
+        let cells = |edges: Vec<Vec<usize>>, labels: &[usize]| {
+            let mut edges: Vec<Vec<usize>> = edges
+                .into_iter()
+                .map(|edge| {
+                    let mut edge: Vec<usize> = edge
+                        .iter()
+                        .map(|l| labels.binary_search(l).expect("every label is a node"))
+                        .collect();
+                    edge.sort();
+                    edge
+                })
+                .collect();
+            edges.sort();
+            edges
+        };
+        let rect = Builder::rect(width, height).build();
+        let rect_edges = rect.iter().flat_map(|g| g.labeled_hyperedges()).collect();
+        let rect_labels: Vec<usize> = (0..width * height).collect();
+        if cells(self.labeled_hyperedges(), &labels) != cells(rect_edges, &rect_labels) {
+            return Err(GridError::NotARect { width, height });
+        }
+        let cell_width = labels
+            .iter()
+            .map(|l| l.to_string().len())
+            .max()
+            .unwrap_or(0);
+        let mut grid = String::new();
+        for row in labels.chunks(width.max(1)) {
+            let cells: Vec<String> = row.iter().map(|l| format!("{l:>cell_width$}")).collect();
+            grid.push_str(&cells.join(" "));
+            grid.push('\n');
+        }
+        Ok(grid)
+    }
+
+    /// Returns a deterministic byte encoding of the isomorphism class of the game.
+    ///
+    /// The encoding holds the node count, the edge count and each edge of
+    /// [`StructuredHypergraph::canonical_form`] as its length followed by its node
+    /// indices, all as LEB128 varints. It ignores the original labels, so
+    /// isomorphic games produce identical bytes and non-isomorphic games differ.
+    ///
+    /// [`StructuredHypergraph::canonical_form`]: crate::hypergraph::StructuredHypergraph::canonical_form
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        let edges = self.graph.canonical_form();
+        let mut bytes = Vec::new();
+        push_varint(&mut bytes, self.nr_nodes());
+        push_varint(&mut bytes, edges.len());
+        for edge in edges {
+            push_varint(&mut bytes, edge.len());
+            edge.iter().for_each(|&n| push_varint(&mut bytes, n));
+        }
+        bytes
+    }
+
+    /// Returns a 64-bit hash of [`TakingGame::canonical_bytes`] that is stable across
+    /// platforms, processes and crate versions sharing the byte encoding.
+    ///
+    /// The algorithm is 64-bit FNV-1a: starting from the offset basis
+    /// `0xcbf29ce484222325`, every byte is XORed in and the state multiplied by the
+    /// prime `0x100000001b3`, wrapping. Unlike [`Hash`](std::hash::Hash), it never depends on
+    /// the hasher, its seed or the pointer width, so it can key external databases.
+    ///
+    /// Like the byte encoding, the hash is invariant under isomorphism, so
+    /// relabelled copies of a game share a key.
+    pub fn stable_hash(&self) -> u64 {
+        self.canonical_bytes()
+            .iter()
+            .fold(0xcbf29ce484222325, |hash, &byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GridError;
+    use crate::{builder::Builder, taking_game::TakingGame};
+
+    #[test]
+    fn test_write_hypergraph_round_trip() {
+        let g = Builder::rect(3, 2).build_one().unwrap();
+        let mut buffer = Vec::new();
+        g.write_hypergraph(&mut buffer).unwrap();
+        let read = Builder::from_dimacs_like(buffer.as_slice())
+            .unwrap()
+            .build_one()
+            .unwrap();
+        assert_eq!(read, g);
+        let mut labels = read.nodes().to_vec();
+        labels.sort();
+        assert_eq!(labels, (0..6).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_graph6_round_trip() {
+        let g = Builder::kayles(5).build_one().unwrap();
+        let encoded = g.to_graph6().unwrap();
+        let decoded = Builder::from_graph6(&encoded).unwrap().build_one().unwrap();
+        assert_eq!(decoded, g);
+
+        let triangle = Builder::from_graph6("Bw").unwrap().build_one().unwrap();
+        assert_eq!(triangle.nr_nodes(), 3);
+        assert_eq!(triangle.to_graph6().unwrap(), "Bw");
+
+        let heap = Builder::heap(3).build_one().unwrap();
+        assert!(heap.to_graph6().is_none());
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let kayles = Builder::kayles(4).build_one().unwrap();
+        let dot = kayles.to_dot();
+        assert!(dot.starts_with("graph {") && dot.trim_end().ends_with('}'));
+        assert_eq!(dot.lines().filter(|l| l.contains("--")).count(), 3);
+        assert_eq!(dot.lines().filter(|l| l.contains("label=")).count(), 4);
+
+        let heap = Builder::heap(3).build_one().unwrap();
+        let dot = heap.to_dot();
+        assert_eq!(
+            dot.lines().filter(|l| l.contains("shape=square")).count(),
+            1
+        );
+        assert_eq!(dot.lines().filter(|l| l.contains("--")).count(), 3);
+    }
+
+    #[test]
+    fn test_display_as_grid() {
+        let g = Builder::rect(3, 2).build_one().unwrap();
+        let grid = g.display_as_grid(3, 2).unwrap();
+        assert_eq!(grid, "0 1 2\n3 4 5\n");
+        assert_eq!(grid.lines().count(), 2);
+        assert!(grid.lines().all(|row| row.split_whitespace().count() == 3));
+
+        assert_eq!(
+            g.display_as_grid(4, 2),
+            Err(GridError::NodeCount {
+                width: 4,
+                height: 2,
+                nodes: 6
+            })
+        );
+        // Six nodes, but the rows have length 2.
+        assert_eq!(
+            g.display_as_grid(2, 3),
+            Err(GridError::NotARect {
+                width: 2,
+                height: 3
+            })
+        );
+        let cycle = Builder::cycle(6).build_one().unwrap();
+        assert!(cycle.display_as_grid(3, 2).is_err());
+
+        let sparse = Builder::from_labeled_hyperedges(vec![
+            vec![10, 20],
+            vec![30, 40],
+            vec![10, 30],
+            vec![20, 40],
+        ])
+        .build_one()
+        .unwrap();
+        assert_eq!(sparse.display_as_grid(2, 2).unwrap(), "10 20\n30 40\n");
+        assert_eq!(
+            g.display_as_grid(2, 3).unwrap_err().to_string(),
+            "the game is not a 2x3 rect"
+        );
+    }
+
+    #[test]
+    fn test_canonical_bytes() {
+        let games: Vec<_> = [
+            vec![vec![0, 1], vec![1, 2]],
+            vec![vec![0, 3], vec![3, 2]],
+            vec![vec![9, 4], vec![4, 2], vec![9], vec![2, 4]],
+        ]
+        .into_iter()
+        .map(|edges| Builder::from_hyperedges(edges).build_one().unwrap())
+        .collect();
+        let bytes = games[0].canonical_bytes();
+        assert_eq!(bytes[..2], [3, 2]);
+        assert!(games.iter().all(|g| g.canonical_bytes() == bytes));
+
+        let heap = Builder::heap(3).build_one().unwrap();
+        assert_ne!(heap.canonical_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_canonical_bytes_of_symmetric_games() {
+        // Refinement alone leaves all nodes of a cycle tied.
+        let cycle = Builder::cycle(6).build_one().unwrap();
+        let shuffled = Builder::from_hyperedges(vec![
+            vec![4, 1],
+            vec![2, 4],
+            vec![0, 3],
+            vec![2, 0],
+            vec![1, 5],
+            vec![5, 3],
+        ])
+        .build_one()
+        .unwrap();
+        assert!(cycle.is_isomorphic_to(&shuffled));
+        assert_eq!(cycle.canonical_bytes(), shuffled.canonical_bytes());
+        let triangles = TakingGame::from_hyperedges_raw(vec![
+            vec![0, 1],
+            vec![1, 2],
+            vec![2, 0],
+            vec![3, 4],
+            vec![4, 5],
+            vec![5, 3],
+        ]);
+        assert_ne!(cycle.canonical_bytes(), triangles.canonical_bytes());
+    }
+
+    #[test]
+    fn test_stable_hash() {
+        let heap = Builder::heap(3).build_one().unwrap();
+        assert_eq!(heap.canonical_bytes(), vec![3, 1, 3, 0, 1, 2]);
+        assert_eq!(heap.stable_hash(), 0x6128585feb1744f9);
+
+        let relabelled = Builder::from_hyperedges(vec![vec![4, 9, 7]])
+            .build_one()
+            .unwrap();
+        assert_eq!(relabelled.stable_hash(), heap.stable_hash());
+        let kayles = Builder::kayles(3).build_one().unwrap();
+        assert_ne!(kayles.stable_hash(), heap.stable_hash());
+
+        let cycle = Builder::cycle(6).build_one().unwrap();
+        let relabelled = Builder::from_hyperedges(vec![
+            vec![3, 0],
+            vec![5, 1],
+            vec![4, 2],
+            vec![0, 5],
+            vec![1, 4],
+            vec![2, 3],
+        ])
+        .build_one()
+        .unwrap();
+        assert_eq!(relabelled.stable_hash(), cycle.stable_hash());
+    }
+}
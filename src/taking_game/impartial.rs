@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use evaluator::Impartial;
 use itertools::Itertools;
 use rayon::prelude::*;
@@ -8,40 +10,277 @@ use crate::{
 };
 
 impl Impartial for TakingGame {
-    /// Return the maximum possible nimber for this game.
+    /// Return the maximum possible nimber for this game, see [`TakingGame::tight_max_nimber`].
     ///
-    /// If the game has a symmetry, the nimber is 0. Otherwise, it is
-    /// bounded above by the number of nodes.
+    /// If the game has a symmetry, the nimber is 0. Otherwise, it is bounded
+    /// above by both the number of nodes and the number of moves, which is
+    /// exact for heaps.
     fn get_max_nimber(&self) -> Option<usize> {
-        match self.find_symmetry() {
-            Some(_) => Some(0),
-            None => Some(self.graph.nr_nodes()),
-        }
+        Some(self.tight_max_nimber())
     }
 
     /// Generate move splits by considering one representative
     /// from each structural equivalence class of edges.
     fn get_split_moves(&self) -> Vec<Vec<TakingGame>> {
+        self.get_split_moves_min(1)
+    }
+}
+
+impl TakingGame {
+    /// Generate move splits like [`Impartial::get_split_moves`], but only
+    /// for moves removing at least `min` nodes.
+    ///
+    /// This models variants where each move must take at least `min` tokens.
+    pub fn get_split_moves_min(&self, min: usize) -> Vec<Vec<TakingGame>> {
         if self.graph.is_empty() {
             return vec![];
         }
+        let sizes = self.edge_subtraction_sets();
+        if !sizes.is_empty() {
+            return self.get_split_moves_constrained(&sizes, min);
+        }
         self.graph
             .get_edge_partitions()
             .par_iter()
-            .flat_map(|e| self.get_moves_of_edge(e.start))
+            .flat_map(|e| self.get_moves_of_edge(e.start, min))
             .collect()
     }
-}
 
-impl TakingGame {
-    /// Generate all moves resulting from removing nodes belonging
+    /// Returns the game where a move on the canonical hyperedge `e` may only remove
+    /// a number of nodes listed in `allowed[&e]`.
+    ///
+    /// This models subtraction-set variants. The constraints are stored with the
+    /// original node labels of each hyperedge, so they carry over to every position
+    /// reached by [`Impartial::get_split_moves`] and are respected by an
+    /// [`evaluator::Evaluator`]. After a move, a hyperedge is restricted by every
+    /// constrained hyperedge containing all of its remaining nodes and may remove
+    /// the sizes allowed by any of them. Hyperedges without a constraint are
+    /// unrestricted. Moves still drop hyperedges contained in others, as in the
+    /// unconstrained game, so a smaller unrestricted hyperedge inside a constrained
+    /// one loses its moves; constraints are exact for disjoint hyperedges like heaps.
+    ///
+    /// Panics if a key is not a hyperedge index.
+    pub fn with_edge_subtraction_sets(&self, allowed: &HashMap<usize, Vec<usize>>) -> TakingGame {
+        let mut subtraction_sets = self.subtraction_sets.to_vec();
+        subtraction_sets.extend(allowed.iter().map(|(&edge, sizes)| {
+            let mut labels: Vec<usize> = self.graph.hyperedges()[edge]
+                .iter()
+                .map(|n| self.nodes()[n])
+                .collect();
+            labels.sort_unstable();
+            (labels, sizes.clone())
+        }));
+        subtraction_sets.sort();
+        TakingGame {
+            graph: self.graph.clone(),
+            subtraction_sets: subtraction_sets.into(),
+        }
+    }
+
+    /// Returns the move sizes allowed on each canonical hyperedge, see
+    /// [`TakingGame::with_edge_subtraction_sets`].
+    ///
+    /// Unrestricted hyperedges have `None`. The list is empty if no hyperedge is
+    /// restricted.
+    pub fn edge_subtraction_sets(&self) -> Vec<Option<Vec<usize>>> {
+        if self.subtraction_sets.is_empty() {
+            return vec![];
+        }
+        let sizes: Vec<Option<Vec<usize>>> = self
+            .graph
+            .hyperedges()
+            .iter()
+            .map(|edge| {
+                let mut allowed: Option<Vec<usize>> = None;
+                for (labels, sizes) in self.subtraction_sets.iter() {
+                    if edge
+                        .iter()
+                        .all(|n| labels.binary_search(&self.nodes()[n]).is_ok())
+                    {
+                        allowed.get_or_insert_default().extend(sizes);
+                    }
+                }
+                allowed.map(|mut sizes| {
+                    sizes.sort_unstable();
+                    sizes.dedup();
+                    sizes
+                })
+            })
+            .collect();
+        if sizes.iter().all(Option::is_none) {
+            return vec![];
+        }
+        sizes
+    }
+
+    /// Generate the moves of a game with subtraction sets, given the move sizes
+    /// `sizes` of [`TakingGame::edge_subtraction_sets`].
+    ///
+    /// Structurally equivalent edges and nodes need not be equivalent under the
+    /// constraints, so every subset of every hyperedge is considered.
+    fn get_split_moves_constrained(
+        &self,
+        sizes: &[Option<Vec<usize>>],
+        min: usize,
+    ) -> Vec<Vec<TakingGame>> {
+        let mut masks: Vec<Bitset128> = self
+            .graph
+            .hyperedges()
+            .iter()
+            .zip(sizes)
+            .flat_map(|(edge, sizes)| {
+                let nodes: Vec<usize> = edge.iter().collect();
+                let counts = match sizes {
+                    Some(sizes) => sizes.clone(),
+                    None => (1..=nodes.len()).collect(),
+                };
+                counts
+                    .into_iter()
+                    .filter(|&count| count >= min.max(1))
+                    .flat_map(move |count| {
+                        nodes
+                            .clone()
+                            .into_iter()
+                            .combinations(count)
+                            .map(|removed| Bitset128::from_slice(&removed))
+                    })
+            })
+            .collect();
+        masks.sort();
+        masks.dedup();
+        masks
+            .into_par_iter()
+            .map(|mask| self.with_nodes_from_set_removed(mask))
+            .collect()
+    }
+
+    /// Generate move splits like [`Impartial::get_split_moves`], keeping only moves
+    /// that leave exactly one connected component.
+    ///
+    /// This models variants where a move must not disconnect the board. Moves
+    /// taking all remaining nodes leave no component and are excluded as well.
+    pub fn get_split_moves_connected(&self) -> Vec<Vec<TakingGame>> {
+        let mut moves = self.get_split_moves();
+        moves.retain(|parts| parts.len() == 1);
+        moves
+    }
+
+    /// Yields the moves of [`Impartial::get_split_moves`] lazily, in the same order.
+    ///
+    /// Each move is only built when requested, so callers that stop early, e.g.
+    /// on the first move to a P-position, never materialize the remaining moves.
+    pub fn moves_iter(&self) -> impl Iterator<Item = Vec<TakingGame>> + '_ {
+        self.masks_iter()
+            .map(|mask| self.with_nodes_from_set_removed(mask))
+    }
+
+    /// Yields the canonical nodes removed by each move of [`TakingGame::moves_iter`].
+    pub(super) fn masks_iter(&self) -> impl Iterator<Item = Bitset128> + '_ {
+        let partitions = if self.graph.is_empty() {
+            vec![]
+        } else {
+            self.graph.get_edge_partitions()
+        };
+        partitions
+            .into_iter()
+            .flat_map(move |e| self.get_masks_of_edge(e.start, 1))
+    }
+
+    /// Generate move splits like [`Impartial::get_split_moves`], pairing each move
+    /// with the index of the edge partition it was generated from.
+    pub fn split_move_groups(&self) -> Vec<(usize, Vec<TakingGame>)> {
+        if self.graph.is_empty() {
+            return vec![];
+        }
+        self.graph
+            .get_edge_partitions()
+            .par_iter()
+            .enumerate()
+            .flat_map(|(i, e)| self.get_moves_of_edge(e.start, 1).map(move |m| (i, m)))
+            .collect()
+    }
+
+    /// Returns every distinct single component reachable in one move.
+    ///
+    /// Unlike [`Impartial::get_split_moves`], the grouping of components per move
+    /// is discarded and equal components are deduplicated.
+    pub fn all_children(&self) -> Vec<TakingGame> {
+        let mut children: Vec<TakingGame> = self.get_split_moves().into_iter().flatten().collect();
+        children.sort();
+        children.dedup();
+        children
+    }
+
+    /// Returns the number of moves [`Impartial::get_split_moves`] would generate,
+    /// without generating them.
+    ///
+    /// Saturates at `usize::MAX` for games with an astronomical number of moves.
+    pub fn move_count(&self) -> usize {
+        let node_partitions = self.graph.get_node_partitions();
+        self.graph
+            .get_edge_partitions()
+            .iter()
+            .map(|e| {
+                self.graph.hyperedges()[e.start]
+                    .partition(&node_partitions)
+                    .iter()
+                    .fold(1usize, |acc, part| acc.saturating_mul(part.len() + 1))
+                    - 1
+            })
+            .fold(0, usize::saturating_add)
+    }
+
+    /// Returns an upper bound on the nimber that is at least as tight as
+    /// [`Impartial::get_max_nimber`] for asymmetric games.
+    ///
+    /// The bound is the minimum of two valid bounds:
+    /// - the node count, as every move removes at least one node,
+    /// - the number of moves, as the mex of `k` values is at most `k`.
+    ///
+    /// With subtraction sets, see [`TakingGame::with_edge_subtraction_sets`], only
+    /// the node count is used, as symmetries and move counts ignore the constraints.
+    pub fn tight_max_nimber(&self) -> usize {
+        if !self.edge_subtraction_sets().is_empty() {
+            return self.nr_nodes();
+        }
+        if self.find_symmetry().is_some() {
+            return 0;
+        }
+        self.nr_nodes().min(self.move_count())
+    }
+
+    /// Generate move splits like [`Impartial::get_split_moves`], unless the
+    /// number of moves exceeds `cap`, in which case `None` is returned.
+    ///
+    /// The cartesian product over node partitions can explode for wide edges
+    /// with many partitions. No subset of the moves is returned in that case:
+    /// the nimber is the mex over *all* children, so a truncated move list
+    /// could under-approximate the mex and silently produce a wrong nimber.
+    /// Callers should fall back to a different strategy instead.
+    pub fn get_split_moves_capped(&self, cap: usize) -> Option<Vec<Vec<TakingGame>>> {
+        if self.move_count() > cap {
+            return None;
+        }
+        Some(self.get_split_moves())
+    }
+
+    /// Generate all moves resulting from removing at least `min` nodes belonging
     /// to a given hyperedge, partitioned by structural equivalence.
     fn get_moves_of_edge(
         &self,
         hyperedge: usize,
+        min: usize,
     ) -> impl ParallelIterator<Item = Vec<TakingGame>> + '_ {
-        let partitioned_hyperedge =
-            self.graph.hyperedges()[hyperedge].partition(&self.graph.get_node_partitions());
+        self.get_masks_of_edge(hyperedge, min)
+            .into_par_iter()
+            .map(|mask| self.with_nodes_from_set_removed(mask))
+    }
+
+    /// Returns the sets of canonical node indices removed by the moves of
+    /// [`TakingGame::get_moves_of_edge`], in the same order.
+    fn get_masks_of_edge(&self, hyperedge: usize, min: usize) -> Vec<Bitset128> {
+        let edge = &self.graph.hyperedges()[hyperedge];
+        let partitioned_hyperedge = edge.partition(&self.graph.get_node_partitions());
 
         let nodes_to_remove_per_part = partitioned_hyperedge.into_iter().map(|mut part| {
             let mut nodes_to_remove_in_part = Vec::with_capacity(part.len() + 1);
@@ -65,8 +304,29 @@ impl TakingGame {
                 nodes_to_remove
             })
             .skip(1)
-            .par_bridge()
-            .map(|mask| self.with_nodes_from_set_removed(mask))
+            .filter(|nodes_to_remove| nodes_to_remove.len() >= min)
+            .collect()
+    }
+
+    /// Pairs every move of [`Impartial::get_split_moves`], in the same order, with
+    /// the original node labels it removes.
+    pub fn enumerate_moves(&self) -> Vec<(Vec<usize>, Vec<TakingGame>)> {
+        if self.graph.is_empty() {
+            return vec![];
+        }
+        let masks: Vec<Bitset128> = self
+            .graph
+            .get_edge_partitions()
+            .iter()
+            .flat_map(|e| self.get_masks_of_edge(e.start, 1))
+            .collect();
+        masks
+            .into_par_iter()
+            .map(|mask| {
+                let labels: Vec<usize> = mask.iter().map(|n| self.nodes()[n]).collect();
+                (labels, self.with_nodes_from_set_removed(mask))
+            })
+            .collect()
     }
 
     pub fn with_nodes_removed(&self, nodes: &[usize]) -> Vec<Self> {
@@ -77,14 +337,41 @@ impl TakingGame {
             .collect();
         self.with_nodes_from_set_removed(Bitset128::from_slice(&mask))
     }
+    /// Like [`TakingGame::with_nodes_removed`], but pairs every resulting component
+    /// with its surviving original node labels, in ascending order.
+    ///
+    /// The labels of all components together are the labels of `self` minus `nodes`.
+    pub fn with_nodes_removed_labeled(&self, nodes: &[usize]) -> Vec<(Self, Vec<usize>)> {
+        self.with_nodes_removed(nodes)
+            .into_iter()
+            .map(|part| {
+                let mut labels = part.nodes().to_vec();
+                labels.sort_unstable();
+                (part, labels)
+            })
+            .collect()
+    }
+    /// Removes every node of the canonical hyperedge `edge_index` at once and
+    /// returns the resulting components.
+    ///
+    /// This is the single move of "take a whole set" rule variants; it is not
+    /// restricted to the structural partitions used by [`Impartial::get_split_moves`].
+    /// Panics if `edge_index` is out of range.
+    pub fn take_whole_edge(&self, edge_index: usize) -> Vec<Self> {
+        let edge = self.graph.hyperedges()[edge_index].clone();
+        self.with_nodes_from_set_removed(edge)
+    }
     /// Return new game states with the given nodes removed.
     ///
     /// Each hyperedge is filtered to exclude the removed nodes.
-    fn with_nodes_from_set_removed(&self, mask: Bitset128) -> Vec<Self> {
+    pub(super) fn with_nodes_from_set_removed(&self, mask: Bitset128) -> Vec<Self> {
         self.graph
             .minus(mask)
             .into_iter()
-            .map(|graph| Self { graph })
+            .map(|graph| Self {
+                graph,
+                subtraction_sets: self.subtraction_sets.clone(),
+            })
             .collect()
     }
 }
@@ -92,7 +379,8 @@ impl TakingGame {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::builder::Builder;
+    use crate::builder::{Builder, get_known_games};
+    use evaluator::Evaluator;
 
     #[test]
     fn test_simple_move_generation() {
@@ -118,6 +406,44 @@ mod tests {
         assert_eq!(with_one_removed[0].nr_nodes(), 2);
     }
 
+    #[test]
+    fn test_with_nodes_removed_labeled() {
+        let g = Builder::kayles(4).build_one().unwrap();
+        for removed in [vec![1], vec![0, 3], vec![2, 3]] {
+            let parts = g.with_nodes_removed_labeled(&removed);
+            let mut surviving: Vec<usize> = parts
+                .iter()
+                .flat_map(|(_, labels)| labels.clone())
+                .collect();
+            surviving.sort();
+            let complement: Vec<usize> = (0..4).filter(|n| !removed.contains(n)).collect();
+            assert_eq!(surviving, complement);
+            for (part, labels) in &parts {
+                assert_eq!(part.nr_nodes(), labels.len());
+            }
+        }
+        let split = g.with_nodes_removed_labeled(&[1]);
+        assert_eq!(split.len(), 2);
+    }
+
+    #[test]
+    fn test_take_whole_edge() {
+        let heap = Builder::heap(4).build_one().unwrap();
+        assert!(heap.take_whole_edge(0).is_empty());
+
+        // Taking a middle pair of Kayles splits the row in two.
+        let g = Builder::kayles(5).build_one().unwrap();
+        let splits: Vec<usize> = (0..g.graph.hyperedges().len())
+            .map(|e| g.take_whole_edge(e).len())
+            .collect();
+        assert!(splits.contains(&2));
+        for e in 0..g.graph.hyperedges().len() {
+            let removed = g.graph.hyperedges()[e].len();
+            let remaining: usize = g.take_whole_edge(e).iter().map(|p| p.nr_nodes()).sum();
+            assert_eq!(remaining + removed, g.nr_nodes());
+        }
+    }
+
     #[test]
     fn test_split_moves_single_edge() {
         // Graph with a single hyperedge of 5 nodes
@@ -159,4 +485,179 @@ mod tests {
         // At least some moves should result in multiple components
         assert!(moves.iter().any(|comp| comp.len() > 1));
     }
+
+    #[test]
+    fn test_split_moves_min() {
+        let g = Builder::heap(3).build_one().unwrap();
+        let moves = g.get_split_moves_min(2);
+
+        // Only removing 2 or 3 tokens is allowed
+        assert_eq!(moves.len(), 2);
+        assert!(moves.iter().flatten().all(|comp| comp.nr_nodes() <= 1));
+        assert_eq!(g.get_split_moves_min(1).len(), g.get_split_moves().len());
+    }
+
+    #[test]
+    fn test_split_move_groups() {
+        let g = Builder::rect(3, 4).build_one().unwrap();
+        let groups = g.split_move_groups();
+        let partition_count = g.graph.get_edge_partitions().len();
+        assert_eq!(groups.len(), g.get_split_moves().len());
+        assert!(groups.iter().all(|(i, _)| *i < partition_count));
+    }
+
+    #[test]
+    fn test_with_edge_subtraction_sets() {
+        // The subtraction game {1, 2} on a single heap has nimbers n mod 3.
+        let eval = Evaluator::new();
+        let allowed = HashMap::from([(0, vec![1, 2])]);
+        for n in 1..=9 {
+            let heap = Builder::heap(n).build_one().unwrap();
+            let game = heap.with_edge_subtraction_sets(&allowed);
+            assert_eq!(eval.get_nimber(&game), Some(n % 3));
+            assert_ne!(game, heap);
+            assert_eq!(eval.get_nimber(&heap), Some(n));
+        }
+
+        // Constraints follow the labels, not the canonical edge indices.
+        let shifted = Builder::from_labeled_hyperedges(vec![vec![10, 11, 12]])
+            .build_one()
+            .unwrap()
+            .with_edge_subtraction_sets(&allowed);
+        let heap = Builder::heap(3).build_one().unwrap();
+        assert_eq!(shifted, heap.with_edge_subtraction_sets(&allowed));
+        assert!(shifted.get_split_moves().iter().all(|parts| {
+            parts
+                .iter()
+                .all(|p| p.edge_subtraction_sets() == vec![Some(vec![1, 2])])
+        }));
+
+        let g = Builder::kayles(4).build_one().unwrap();
+        assert_eq!(g.with_edge_subtraction_sets(&HashMap::new()), g);
+        assert_eq!(
+            g.with_edge_subtraction_sets(&HashMap::new())
+                .get_split_moves(),
+            g.get_split_moves()
+        );
+
+        // Forbidding every move on one row of a square leaves the other row and
+        // both columns, i.e. taking any single node or one of three pairs. The
+        // square alone is a second player win.
+        let square = Builder::rect(2, 2).build_one().unwrap();
+        let row = (0..4)
+            .find(|&e| square.graph.hyperedges()[e].len() == 2)
+            .unwrap();
+        let restricted = square.with_edge_subtraction_sets(&HashMap::from([(row, vec![])]));
+        assert_eq!(restricted.edge_subtraction_sets()[row], Some(vec![]));
+        assert_eq!(restricted.get_split_moves().len(), 4 + 3);
+        assert_eq!(eval.get_nimber(&square), Some(0));
+        assert_ne!(eval.get_nimber(&restricted), Some(0));
+    }
+
+    #[test]
+    fn test_split_moves_connected() {
+        let path = Builder::kayles(6).build_one().unwrap();
+        let connected = path.get_split_moves_connected();
+        assert!(connected.iter().all(|parts| parts.len() == 1));
+        let all = path.get_split_moves();
+        assert!(all.iter().any(|parts| parts.len() == 2));
+        assert_eq!(
+            connected.len(),
+            all.iter().filter(|parts| parts.len() == 1).count()
+        );
+
+        let heap = Builder::heap(3).build_one().unwrap();
+        assert_eq!(heap.get_split_moves_connected().len(), 2);
+    }
+
+    #[test]
+    fn test_moves_iter() {
+        for k in get_known_games() {
+            for part in k.get_parts() {
+                let lazy: Vec<Vec<TakingGame>> = part.moves_iter().collect();
+                assert_eq!(lazy, part.get_split_moves());
+            }
+        }
+        let g = Builder::rect(4, 4).build_one().unwrap();
+        assert_eq!(g.moves_iter().take(3).count(), 3);
+    }
+
+    #[test]
+    fn test_all_children() {
+        let g = Builder::kayles(4).build_one().unwrap();
+        let children = g.all_children();
+        let total: usize = g.get_split_moves().iter().map(|m| m.len()).sum();
+        assert!(children.len() < total);
+        for (i, a) in children.iter().enumerate() {
+            assert!(children[i + 1..].iter().all(|b| a != b));
+        }
+    }
+
+    #[test]
+    fn test_move_count() {
+        for g in [
+            Builder::heap(5).build_one().unwrap(),
+            Builder::kayles(6).build_one().unwrap(),
+            Builder::rect(3, 3).build_one().unwrap(),
+        ] {
+            assert_eq!(g.move_count(), g.get_split_moves().len());
+        }
+    }
+
+    #[test]
+    fn test_tight_max_nimber() {
+        let eval = Evaluator::new();
+        for k in get_known_games() {
+            for part in k.get_parts() {
+                let bound = part.tight_max_nimber();
+                assert!(bound <= part.nr_nodes());
+                assert!(bound >= eval.get_nimber(part).unwrap());
+                assert_eq!(part.get_max_nimber(), Some(bound));
+            }
+        }
+        for size in 1..6 {
+            let heap = Builder::heap(size).build_one().unwrap();
+            assert_eq!(heap.tight_max_nimber(), size);
+        }
+    }
+
+    #[test]
+    fn test_enumerate_moves() {
+        let heap = Builder::heap(4).build_one().unwrap();
+        let moves = heap.enumerate_moves();
+        assert_eq!(moves.len(), 4);
+        assert!(moves.iter().all(|(removed, _)| !removed.is_empty()));
+        for (i, (a, _)) in moves.iter().enumerate() {
+            assert!(moves[i + 1..].iter().all(|(b, _)| a != b));
+        }
+        let parts: Vec<Vec<TakingGame>> = moves.into_iter().map(|(_, parts)| parts).collect();
+        assert_eq!(parts, heap.get_split_moves());
+
+        let labelled = Builder::from_hyperedges(vec![vec![10, 20], vec![20, 30]])
+            .build_one()
+            .unwrap();
+        for (removed, parts) in labelled.enumerate_moves() {
+            assert!(removed.iter().all(|n| [10, 20, 30].contains(n)));
+            let remaining: usize = parts.iter().map(|p| p.nr_nodes()).sum();
+            assert_eq!(remaining + removed.len(), 3);
+        }
+
+        let sparse = Builder::from_labeled_hyperedges(vec![vec![100, 500], vec![500, 900]])
+            .build_one()
+            .unwrap();
+        for (removed, parts) in sparse.enumerate_moves() {
+            let mut labels: Vec<usize> = parts.iter().flat_map(|p| p.nodes().to_vec()).collect();
+            labels.extend(&removed);
+            labels.sort();
+            assert_eq!(labels, vec![100, 500, 900]);
+        }
+    }
+
+    #[test]
+    fn test_split_moves_capped() {
+        let g = Builder::heap(100).build_one().unwrap();
+        assert_eq!(g.move_count(), 100);
+        assert!(g.get_split_moves_capped(99).is_none());
+        assert_eq!(g.get_split_moves_capped(100).unwrap().len(), 100);
+    }
 }
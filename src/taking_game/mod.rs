@@ -1,25 +1,129 @@
+use std::cmp::{Ordering, Reverse};
 use std::fmt::Display;
 use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+mod evaluation;
+mod formats;
 mod impartial;
+#[cfg(feature = "serde")]
+mod serialization;
+mod session;
+mod shapes;
 mod symmetries;
+mod validation;
+
+pub use evaluation::{MoveStats, NimberResult, SearchStats, detect_period, mex};
+pub use formats::GridError;
+pub use session::{GameSession, IllegalMove};
+pub use validation::{BuildError, Diagnostic};
+
+use union_find::{QuickUnionUf, UnionByRank, UnionFind};
 
 use crate::hypergraph::Bitset128;
 use crate::hypergraph::Set;
 use crate::hypergraph::StructuredHypergraph;
 
 /// A generalized representation of an impartial "taking game".
-#[derive(Clone, PartialEq, Eq, Debug, PartialOrd, Ord)]
+///
+/// Equality, ordering and hashing only consider the canonical hyperedges and the
+/// move sizes allowed on them, never the original node labels, so they are
+/// consistent with each other.
+///
+/// With the `serde` feature, games serialize as their canonical hyperedges and node labels.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        into = "serialization::SerializedGame",
+        try_from = "serialization::SerializedGame"
+    )
+)]
 pub struct TakingGame {
     graph: StructuredHypergraph<Bitset128>,
+    /// Constrained hyperedges as their sorted original node labels, each with the
+    /// numbers of nodes a move on it may remove, see
+    /// [`TakingGame::with_edge_subtraction_sets`].
+    subtraction_sets: Arc<[(Vec<usize>, Vec<usize>)]>,
+}
+/// Converts hyperedges of node labels into bitsets.
+///
+/// Panics if a label does not fit into a [`Bitset128`], which would otherwise
+/// silently corrupt the hyperedge.
+fn to_bitsets(edges: &[Vec<usize>]) -> Vec<Bitset128> {
+    if let Some(label) = edges.iter().flatten().find(|&&n| n >= Bitset128::CAPACITY) {
+        panic!(
+            "node label {label} exceeds the capacity of {} nodes per game",
+            Bitset128::CAPACITY
+        );
+    }
+    edges.iter().map(|s| Bitset128::from_slice(s)).collect()
 }
+
+/// Returns the number of connected components of the game described by `edges`.
+///
+/// Equal to `TakingGame::from_hyperesges(edges).len()`, but only runs the
+/// union-find step, skipping redundant edge removal and canonicalization.
+/// Labels may be arbitrary and are not limited by [`Bitset128::CAPACITY`].
+pub fn component_count(edges: &[Vec<usize>]) -> usize {
+    let mut labels: Vec<usize> = edges.iter().flatten().copied().collect();
+    labels.sort();
+    labels.dedup();
+    let index = |label: &usize| {
+        labels
+            .binary_search(label)
+            .expect("every label was collected")
+    };
+    let mut uf: QuickUnionUf<UnionByRank> = QuickUnionUf::new(labels.len());
+    let mut components = labels.len();
+    for e in edges {
+        if let Some((first, rest)) = e.split_first() {
+            for node in rest {
+                if uf.union(index(first), index(node)) {
+                    components -= 1;
+                }
+            }
+        }
+    }
+    components
+}
+
 impl TakingGame {
+    /// Builds the connected components of the game described by `edges`.
+    ///
+    /// Panics if any node label is not below [`Bitset128::CAPACITY`].
     pub fn from_hyperesges(edges: Vec<Vec<usize>>) -> Vec<Self> {
-        StructuredHypergraph::from_hyperedges(
-            edges.iter().map(|s| Bitset128::from_slice(s)).collect(),
-        )
-        .into_iter()
-        .map(|graph| Self { graph })
-        .collect()
+        StructuredHypergraph::from_hyperedges(to_bitsets(&edges))
+            .into_iter()
+            .map(Self::from_graph)
+            .collect()
+    }
+    /// Builds the connected components of the game described by `edges`, where node
+    /// `i` carries the original label `labels[i]`.
+    ///
+    /// Panics if any node index is not below [`Bitset128::CAPACITY`].
+    pub fn from_hyperedges_with_labels(edges: Vec<Vec<usize>>, labels: Vec<usize>) -> Vec<Self> {
+        StructuredHypergraph::from_hyperedges_with_nodes(to_bitsets(&edges), labels)
+            .into_iter()
+            .map(Self::from_graph)
+            .collect()
+    }
+    /// Builds a single game without canonicalization, for trusted inputs.
+    ///
+    /// Nodes are still flattened and redundant hyperedges removed, but the game is
+    /// not split into components and not canonically ordered. It is therefore NOT
+    /// suitable for equality, hashing or caching; use it only for cheap queries
+    /// such as counting nodes.
+    pub fn from_hyperedges_raw(edges: Vec<Vec<usize>>) -> Self {
+        Self::from_graph(StructuredHypergraph::from_hyperedges_unsorted(to_bitsets(
+            &edges,
+        )))
+    }
+    fn from_graph(graph: StructuredHypergraph<Bitset128>) -> Self {
+        Self {
+            graph,
+            subtraction_sets: Arc::default(),
+        }
     }
     pub fn nr_nodes(&self) -> usize {
         self.graph.nr_nodes()
@@ -27,6 +131,96 @@ impl TakingGame {
     pub fn nodes(&self) -> &[usize] {
         self.graph.nodes()
     }
+    /// Returns true if canonicalization reached a fixpoint when the game was built.
+    ///
+    /// If false, equality and hashing of this game should not be trusted for caching.
+    pub fn canonicalization_converged(&self) -> bool {
+        self.graph.canonicalization_converged()
+    }
+    /// Panics if the internal representation of the game is inconsistent.
+    #[cfg(debug_assertions)]
+    pub fn assert_invariants(&self) {
+        self.graph.assert_invariants();
+    }
+    /// Returns the indices of the hyperedges containing the canonical node `node`.
+    pub fn edges_of_node(&self, node: usize) -> Vec<usize> {
+        self.graph.edges_of_node(node)
+    }
+    /// Returns true if no hyperedge is a subset of another, see
+    /// [`StructuredHypergraph::has_no_redundant_edges`].
+    pub fn has_no_redundant_edges(&self) -> bool {
+        self.graph.has_no_redundant_edges()
+    }
+    /// Returns a one-line human readable summary of the game.
+    pub fn describe(&self) -> String {
+        let max_edge = self.graph.hyperedges().iter().map(|e| e.len()).max();
+        let components = component_count(&self.labeled_hyperedges());
+        format!(
+            "TakingGame: {} nodes, {} edges, max edge {}, {} component{}, symmetric={}",
+            self.nr_nodes(),
+            self.graph.hyperedges().len(),
+            max_edge.unwrap_or(0),
+            components,
+            if components == 1 { "" } else { "s" },
+            self.find_symmetry().is_some()
+        )
+    }
+    /// Returns the canonical hyperedges, each as a list of original node labels.
+    pub fn labeled_hyperedges(&self) -> Vec<Vec<usize>> {
+        self.graph
+            .hyperedges()
+            .iter()
+            .map(|e| e.iter().map(|n| self.nodes()[n]).collect())
+            .collect()
+    }
+    /// Returns the canonical index and the original node labels of the hyperedge
+    /// with the most nodes, or `None` if the game has no hyperedges.
+    ///
+    /// Ties are broken by canonical order, i.e. the first largest hyperedge is returned.
+    pub fn largest_hyperedge(&self) -> Option<(usize, Vec<usize>)> {
+        self.labeled_hyperedges()
+            .into_iter()
+            .enumerate()
+            .min_by_key(|(_, e)| Reverse(e.len()))
+    }
+    /// Returns the components of the game with the hyperedge `edge` added.
+    ///
+    /// `edge` holds original node labels, like [`TakingGame::with_nodes_removed`].
+    /// Labels not in the game become new nodes, so the result may consist of several
+    /// components, while an edge bridging existing and new nodes connects them.
+    /// The result is recanonicalized and keeps all original labels.
+    pub fn with_edge_added(&self, edge: &[usize]) -> Vec<Self> {
+        let mut edges = self.labeled_hyperedges();
+        edges.push(edge.to_vec());
+        let mut labels: Vec<usize> = edges.iter().flatten().copied().collect();
+        labels.sort();
+        labels.dedup();
+        let edges = edges
+            .iter()
+            .map(|e| {
+                e.iter()
+                    .map(|n| labels.binary_search(n).expect("every label was collected"))
+                    .collect()
+            })
+            .collect();
+        Self::from_hyperedges_with_labels(edges, labels)
+    }
+    /// Returns an equal game whose node labels are the canonical indices `0..n`,
+    /// discarding the original labels.
+    pub fn normalize_labels(&self) -> Self {
+        let mut graph = self.graph.clone();
+        graph.normalize_labels();
+        let subtraction_sets = self
+            .edge_subtraction_sets()
+            .into_iter()
+            .zip(graph.hyperedges())
+            .filter_map(|(sizes, edge)| Some((edge.iter().collect(), sizes?)))
+            .collect();
+        Self {
+            graph,
+            subtraction_sets,
+        }
+    }
 }
 impl Display for TakingGame {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -34,8 +228,231 @@ impl Display for TakingGame {
         self.graph.fmt(f)
     }
 }
+impl PartialEq for TakingGame {
+    fn eq(&self, other: &Self) -> bool {
+        self.graph == other.graph && self.edge_subtraction_sets() == other.edge_subtraction_sets()
+    }
+}
+impl Eq for TakingGame {}
+impl Ord for TakingGame {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.graph.cmp(&other.graph).then_with(|| {
+            self.edge_subtraction_sets()
+                .cmp(&other.edge_subtraction_sets())
+        })
+    }
+}
+impl PartialOrd for TakingGame {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
 impl Hash for TakingGame {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.graph.hash(state);
+        self.edge_subtraction_sets().hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TakingGame, component_count};
+    use crate::builder::{Builder, get_known_games};
+
+    #[test]
+    #[should_panic(expected = "node label 128 exceeds the capacity")]
+    fn test_node_label_beyond_capacity_is_rejected() {
+        Builder::from_hyperedges(vec![vec![0, 128]]).build();
+    }
+
+    #[test]
+    fn test_component_count() {
+        let inputs = vec![
+            vec![],
+            vec![vec![]],
+            vec![vec![0, 1], vec![1, 2]],
+            vec![vec![0, 1], vec![2, 3], vec![3, 4], vec![5]],
+            vec![vec![0, 1, 2], vec![0, 1], vec![7], vec![7, 9], vec![4, 4]],
+            vec![vec![3, 1], vec![6, 5], vec![1, 5], vec![10], vec![11, 12]],
+        ];
+        for edges in inputs {
+            assert_eq!(
+                component_count(&edges),
+                TakingGame::from_hyperesges(edges.clone()).len(),
+                "{edges:?}"
+            );
+        }
+        assert_eq!(component_count(&[vec![1000, 2000], vec![5000]]), 2);
+    }
+
+    #[test]
+    fn test_from_hyperedges_raw() {
+        let edges = vec![vec![0, 1], vec![1, 2], vec![0, 1, 2], vec![4, 5], vec![]];
+        let raw = TakingGame::from_hyperedges_raw(edges.clone());
+        let canonical = TakingGame::from_hyperesges(edges);
+        assert_eq!(
+            raw.nr_nodes(),
+            canonical.iter().map(|g| g.nr_nodes()).sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn test_canonicalization_converged() {
+        for k in get_known_games() {
+            assert!(k.get_parts().iter().all(|p| p.canonicalization_converged()));
+        }
+        let raw = TakingGame::from_hyperedges_raw(vec![vec![0, 1]]);
+        assert!(!raw.canonicalization_converged());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn test_assert_invariants_after_moves() {
+        let mut games = Builder::rect(3, 3).build();
+        for _ in 0..4 {
+            games.iter().for_each(|g| g.assert_invariants());
+            let g = games.pop().unwrap();
+            let removed = g.nodes()[0];
+            games.extend(g.with_nodes_removed(&[removed]));
+        }
+        games.iter().for_each(|g| g.assert_invariants());
+        TakingGame::from_hyperedges_raw(vec![vec![0, 1], vec![1, 2]]).assert_invariants();
+    }
+
+    #[test]
+    fn test_heaps_of_different_sizes_do_not_collide() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let hash = |g: &TakingGame| {
+            let mut hasher = DefaultHasher::new();
+            g.hash(&mut hasher);
+            hasher.finish()
+        };
+        let small = Builder::heap(3).build_one().unwrap();
+        let large = Builder::heap(4).build_one().unwrap();
+        assert_ne!(small, large);
+        assert_ne!(hash(&small), hash(&large));
+    }
+
+    #[test]
+    fn test_ord_and_hash_consistent_with_eq() {
+        use std::cmp::Ordering;
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::{BTreeSet, HashSet};
+        use std::hash::{Hash, Hasher};
+
+        let hash = |g: &TakingGame| {
+            let mut hasher = DefaultHasher::new();
+            g.hash(&mut hasher);
+            hasher.finish()
+        };
+        let pairs = [
+            (
+                Builder::kayles(4),
+                Builder::from_hyperedges(vec![vec![9, 2], vec![2, 40], vec![40, 7]]),
+            ),
+            (
+                Builder::rect(3, 2),
+                Builder::from_hyperedges(vec![
+                    vec![5, 3, 1],
+                    vec![0, 2, 4],
+                    vec![5, 4],
+                    vec![3, 2],
+                    vec![1, 0],
+                ]),
+            ),
+        ];
+        let mut games = vec![];
+        for (a, b) in pairs {
+            let (a, b) = (a.build_one().unwrap(), b.build_one().unwrap());
+            assert_ne!(a.nodes(), b.nodes());
+            assert_eq!(a, b);
+            assert_eq!(a.cmp(&b), Ordering::Equal);
+            assert_eq!(hash(&a), hash(&b));
+            games.extend([a, b]);
+        }
+        assert_eq!(games.iter().collect::<BTreeSet<_>>().len(), 2);
+        assert_eq!(games.iter().collect::<HashSet<_>>().len(), 2);
+    }
+
+    #[test]
+    fn test_has_no_redundant_edges() {
+        for k in get_known_games() {
+            assert!(k.get_parts().iter().all(|p| p.has_no_redundant_edges()));
+        }
+    }
+
+    #[test]
+    fn test_describe() {
+        let g = Builder::rect(3, 3).build_one().unwrap();
+        assert_eq!(
+            g.describe(),
+            "TakingGame: 9 nodes, 6 edges, max edge 3, 1 component, symmetric=false"
+        );
+
+        let raw = TakingGame::from_hyperedges_raw(vec![vec![0, 1], vec![2, 3]]);
+        assert!(raw.describe().contains("2 components"));
+    }
+
+    #[test]
+    fn test_edges_of_node() {
+        let g = Builder::rect(2, 2).build_one().unwrap();
+        for node in 0..g.nr_nodes() {
+            assert_eq!(g.edges_of_node(node).len(), 2);
+        }
+    }
+
+    #[test]
+    fn test_largest_hyperedge() {
+        let g = Builder::from_hyperedges(vec![vec![0, 1], vec![1, 2, 3, 4], vec![4, 5, 6]])
+            .build_one()
+            .unwrap();
+        let (index, mut largest) = g.largest_hyperedge().unwrap();
+        assert_eq!(g.labeled_hyperedges()[index], largest);
+        largest.sort();
+        assert_eq!(largest, vec![1, 2, 3, 4]);
+
+        let heap = Builder::heap(3).build_one().unwrap();
+        assert_eq!(
+            heap.largest_hyperedge().map(|(i, e)| (i, e.len())),
+            Some((0, 3))
+        );
+    }
+
+    #[test]
+    fn test_with_edge_added() {
+        let heap = Builder::heap(2).build_one().unwrap();
+        let disjoint = heap.with_edge_added(&[2, 3]);
+        assert_eq!(disjoint.len(), 2);
+        assert!(disjoint.iter().all(|p| *p == heap));
+
+        let path = heap.with_edge_added(&[1, 2]);
+        assert_eq!(path, vec![Builder::kayles(3).build_one().unwrap()]);
+
+        // Bridging the heap {0, 1} to the heap {2, 3} gives Kayles on four nodes.
+        let heaps = TakingGame::from_hyperedges_raw(vec![vec![0, 1], vec![2, 3]]);
+        let bridged = heaps.with_edge_added(&[1, 2]);
+        assert_eq!(bridged.len(), 1);
+        assert!(bridged[0].is_isomorphic_to(&Builder::kayles(4).build_one().unwrap()));
+
+        let square = Builder::rect(2, 2).build_one().unwrap();
+        let mut nodes = square.nodes().to_vec();
+        nodes.sort();
+        let diagonal = square.with_edge_added(&[nodes[0], nodes[3]]);
+        assert_eq!(diagonal.len(), 1);
+        assert_eq!(diagonal[0].nodes().len(), 4);
+    }
+
+    #[test]
+    fn test_normalize_labels() {
+        let g = Builder::rect(3, 3).build_one().unwrap();
+        let moved = g.with_nodes_removed(&[4]).pop().unwrap();
+        let normalized = moved.normalize_labels();
+        assert_eq!(normalized, moved);
+        assert_eq!(
+            normalized.nodes(),
+            (0..moved.nr_nodes()).collect::<Vec<_>>().as_slice()
+        );
     }
 }
@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+
+use crate::hypergraph::{Bitset128, Set};
+
+use super::TakingGame;
+
+/// The serialized form of a [`TakingGame`]: its canonical hyperedges and original node labels.
+///
+/// `hyperedges` holds canonical node indices, `nodes[i]` is the label of node `i`.
+/// `subtraction_sets` is only written for games with subtraction sets, see
+/// [`TakingGame::with_edge_subtraction_sets`].
+#[derive(Serialize, Deserialize)]
+pub(super) struct SerializedGame {
+    nodes: Vec<usize>,
+    hyperedges: Vec<Vec<usize>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    subtraction_sets: Vec<(Vec<usize>, Vec<usize>)>,
+}
+
+impl From<TakingGame> for SerializedGame {
+    fn from(game: TakingGame) -> Self {
+        SerializedGame {
+            nodes: game.nodes().to_vec(),
+            hyperedges: game
+                .graph
+                .hyperedges()
+                .iter()
+                .map(|e| e.iter().collect())
+                .collect(),
+            subtraction_sets: game.subtraction_sets.to_vec(),
+        }
+    }
+}
+
+impl TryFrom<SerializedGame> for TakingGame {
+    type Error = String;
+
+    fn try_from(game: SerializedGame) -> Result<Self, Self::Error> {
+        let node_count = game.nodes.len();
+        if node_count > Bitset128::CAPACITY {
+            return Err(format!(
+                "{node_count} nodes exceed the capacity of {} nodes",
+                Bitset128::CAPACITY
+            ));
+        }
+        if let Some(node) = game.hyperedges.iter().flatten().find(|&&n| n >= node_count) {
+            return Err(format!("node {node} out of range for {node_count} nodes"));
+        }
+        let mut parts = TakingGame::from_hyperedges_with_labels(game.hyperedges, game.nodes);
+        match parts.len() {
+            1 => Ok(TakingGame {
+                subtraction_sets: game.subtraction_sets.into(),
+                ..parts.remove(0)
+            }),
+            n => Err(format!(
+                "expected a single connected game, found {n} components"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{builder::Builder, taking_game::TakingGame};
+
+    #[test]
+    fn test_json_round_trip() {
+        let g = Builder::hyper_cube(2, 3).build_one().unwrap();
+        let json = serde_json::to_string(&g).unwrap();
+        let read: TakingGame = serde_json::from_str(&json).unwrap();
+        assert_eq!(read, g);
+        assert_eq!(read.nodes(), g.nodes());
+    }
+
+    #[test]
+    fn test_rejects_out_of_range_node() {
+        let json = r#"{"nodes":[0,1],"hyperedges":[[0,1],[1,2]]}"#;
+        let err = serde_json::from_str::<TakingGame>(json).unwrap_err();
+        assert!(err.to_string().contains("out of range"));
+
+        let nodes: Vec<usize> = (0..129).collect();
+        let json = format!(r#"{{"nodes":{nodes:?},"hyperedges":[[0,128]]}}"#);
+        let err = serde_json::from_str::<TakingGame>(&json).unwrap_err();
+        assert!(err.to_string().contains("exceed the capacity"));
+    }
+
+    #[test]
+    fn test_sparse_label_round_trip() {
+        let g = Builder::from_labeled_hyperedges(vec![vec![100, 500], vec![500, 900]])
+            .build_one()
+            .unwrap();
+        let json = serde_json::to_string(&g).unwrap();
+        let read: TakingGame = serde_json::from_str(&json).unwrap();
+        assert_eq!(read, g);
+        assert_eq!(read.nodes(), g.nodes());
+    }
+
+    #[test]
+    fn test_subtraction_sets_round_trip() {
+        let g = Builder::heap(4)
+            .build_one()
+            .unwrap()
+            .with_edge_subtraction_sets(&HashMap::from([(0, vec![1, 3])]));
+        let json = serde_json::to_string(&g).unwrap();
+        let read: TakingGame = serde_json::from_str(&json).unwrap();
+        assert_eq!(read, g);
+        assert_eq!(read.edge_subtraction_sets(), vec![Some(vec![1, 3])]);
+        assert!(
+            !serde_json::to_string(&Builder::heap(4).build_one().unwrap())
+                .unwrap()
+                .contains("subtraction_sets")
+        );
+    }
+}
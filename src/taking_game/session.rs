@@ -0,0 +1,145 @@
+use std::fmt::Display;
+
+use super::TakingGame;
+
+/// The reason a move was rejected by [`GameSession::play`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IllegalMove {
+    /// The component index is out of range.
+    NoSuchComponent(usize),
+    /// The move removes no nodes.
+    EmptyMove,
+    /// The node label is not part of the component.
+    UnknownNode(usize),
+    /// The nodes are not all contained in a single hyperedge.
+    NotInOneHyperedge,
+}
+
+impl Display for IllegalMove {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IllegalMove::NoSuchComponent(c) => write!(f, "there is no component {c}"),
+            IllegalMove::EmptyMove => write!(f, "a move must remove at least one node"),
+            IllegalMove::UnknownNode(n) => write!(f, "node {n} is not part of the component"),
+            IllegalMove::NotInOneHyperedge => {
+                write!(f, "the removed nodes must share a hyperedge")
+            }
+        }
+    }
+}
+
+impl std::error::Error for IllegalMove {}
+
+/// A stateful position for interactive play, with a history of previous positions.
+///
+/// The position is held as its list of components; moves are given by original node labels.
+#[derive(Debug, Clone)]
+pub struct GameSession {
+    components: Vec<TakingGame>,
+    history: Vec<Vec<TakingGame>>,
+}
+
+impl GameSession {
+    /// Starts a session from the components of a position.
+    pub fn new(components: Vec<TakingGame>) -> Self {
+        Self {
+            components,
+            history: vec![],
+        }
+    }
+
+    /// Returns the components of the current position.
+    pub fn components(&self) -> &[TakingGame] {
+        &self.components
+    }
+
+    /// Returns true if no moves are left, i.e. the player to move has lost.
+    pub fn is_over(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    /// Removes the nodes with the given labels from the given component.
+    ///
+    /// The nodes must be non-empty and share a hyperedge of the component,
+    /// which may split into several components.
+    pub fn play(&mut self, component: usize, nodes: &[usize]) -> Result<(), IllegalMove> {
+        let game = self
+            .components
+            .get(component)
+            .ok_or(IllegalMove::NoSuchComponent(component))?;
+        if nodes.is_empty() {
+            return Err(IllegalMove::EmptyMove);
+        }
+        if let Some(&n) = nodes.iter().find(|n| !game.nodes().contains(n)) {
+            return Err(IllegalMove::UnknownNode(n));
+        }
+        if !game
+            .labeled_hyperedges()
+            .iter()
+            .any(|e| nodes.iter().all(|n| e.contains(n)))
+        {
+            return Err(IllegalMove::NotInOneHyperedge);
+        }
+        let parts = game.with_nodes_removed(nodes);
+        self.history.push(self.components.clone());
+        self.components.splice(component..=component, parts);
+        Ok(())
+    }
+
+    /// Reverts the last move. Returns false if there was nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop() {
+            Some(previous) => {
+                self.components = previous;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::Builder;
+
+    #[test]
+    fn test_play_and_undo() {
+        let mut session = GameSession::new(Builder::heap(5).build());
+        let labels = session.components()[0].nodes().to_vec();
+
+        assert_eq!(
+            session.play(1, &labels[..1]),
+            Err(IllegalMove::NoSuchComponent(1))
+        );
+        assert_eq!(session.play(0, &[]), Err(IllegalMove::EmptyMove));
+        assert_eq!(session.play(0, &[7]), Err(IllegalMove::UnknownNode(7)));
+
+        session.play(0, &labels[..2]).unwrap();
+        assert_eq!(session.components()[0].nr_nodes(), 3);
+        session.play(0, &labels[2..3]).unwrap();
+        assert_eq!(session.components()[0].nr_nodes(), 2);
+        session.play(0, &labels[3..]).unwrap();
+        assert!(session.is_over());
+
+        assert!(session.undo());
+        assert_eq!(session.components()[0].nr_nodes(), 2);
+        assert!(session.undo());
+        assert!(session.undo());
+        assert_eq!(session.components()[0].nr_nodes(), 5);
+        assert!(!session.undo());
+    }
+
+    #[test]
+    fn test_play_requires_shared_hyperedge() {
+        let mut session = GameSession::new(Builder::kayles(3).build());
+        let mut ends = session.components()[0].nodes().to_vec();
+        ends.sort();
+        assert_eq!(
+            session.play(0, &[ends[0], ends[2]]),
+            Err(IllegalMove::NotInOneHyperedge)
+        );
+        session.play(0, &[ends[1]]).unwrap();
+        assert_eq!(session.components().len(), 2);
+    }
+}
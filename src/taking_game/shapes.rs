@@ -0,0 +1,217 @@
+use itertools::Itertools;
+
+use super::TakingGame;
+use crate::hypergraph::Set;
+
+impl TakingGame {
+    /// Returns true if the game consists of a single hyperedge, i.e. a Nim heap.
+    pub fn is_heap(&self) -> bool {
+        self.graph.hyperedges().len() == 1
+    }
+
+    /// Returns the heap sizes, in ascending order, if the parts form plain Nim.
+    ///
+    /// This is the case iff every part is a single hyperedge without a subtraction
+    /// set, see [`TakingGame::is_heap`]; the nimber is then the XOR of the sizes.
+    /// No parts form the empty Nim position.
+    pub fn as_nim_heaps(parts: &[TakingGame]) -> Option<Vec<usize>> {
+        let mut heaps: Vec<usize> = parts
+            .iter()
+            .map(|p| (p.is_heap() && p.edge_subtraction_sets().is_empty()).then(|| p.nr_nodes()))
+            .collect::<Option<_>>()?;
+        heaps.sort_unstable();
+        Some(heaps)
+    }
+
+    /// Returns the number of hyperedges containing each node, in ascending order.
+    ///
+    /// Isomorphic games have equal degree sequences.
+    pub fn degree_sequence(&self) -> Vec<usize> {
+        let mut degrees: Vec<usize> = self.graph.dual().iter().map(|edges| edges.len()).collect();
+        degrees.sort_unstable();
+        degrees
+    }
+
+    /// Returns the number of nodes of each hyperedge, in ascending order.
+    ///
+    /// Isomorphic games have equal edge size sequences.
+    pub fn edge_size_sequence(&self) -> Vec<usize> {
+        let mut sizes: Vec<usize> = self.graph.hyperedges().iter().map(|e| e.len()).collect();
+        sizes.sort_unstable();
+        sizes
+    }
+
+    /// Collapses every class of twin nodes into a single node and returns the
+    /// collapsed game together with the size of each class.
+    ///
+    /// Two nodes are twins iff they are contained in exactly the same hyperedges.
+    /// Node `i` of the collapsed game keeps the label of the first node of its
+    /// class and stands for `multiplicities[i]` twins. The collapsed game has the
+    /// same hyperedge structure but in general a different nimber, e.g. `heap(3)`
+    /// collapses to `heap(1)`; the original game, and with it the nimber, is
+    /// recovered by expanding each node `i` back into `multiplicities[i]` nodes.
+    pub fn reduce_twin_nodes(&self) -> (TakingGame, Vec<usize>) {
+        let dual = self.graph.dual();
+        let mut classes: Vec<&Vec<usize>> = Vec::new();
+        let mut class_of = Vec::with_capacity(dual.len());
+        let mut labels = Vec::new();
+        let mut sizes = Vec::new();
+        for (node, edges) in dual.iter().enumerate() {
+            let class = match classes.iter().position(|c| *c == edges) {
+                Some(class) => class,
+                None => {
+                    classes.push(edges);
+                    labels.push(self.nodes()[node]);
+                    sizes.push(0);
+                    classes.len() - 1
+                }
+            };
+            sizes[class] += 1;
+            class_of.push(class);
+        }
+        let edges = self
+            .graph
+            .hyperedges()
+            .iter()
+            .map(|e| e.iter().map(|n| class_of[n]).dedup().collect())
+            .collect();
+        let collapsed = TakingGame::from_hyperedges_with_labels(edges, labels.clone())
+            .pop()
+            .unwrap_or_else(|| self.clone());
+        let multiplicities = collapsed
+            .nodes()
+            .iter()
+            .map(|label| {
+                sizes[labels
+                    .iter()
+                    .position(|l| l == label)
+                    .expect("label of a class")]
+            })
+            .collect();
+        (collapsed, multiplicities)
+    }
+
+    /// Recognizes a set of parts forming one heap plus isolated lone nodes.
+    ///
+    /// Returns `(edge_size, lone_count)` if every part is a heap and at most one
+    /// of them has more than one node. Such a position has the closed-form
+    /// nimber `edge_size ^ (lone_count % 2)`.
+    pub fn as_generalized_heap(parts: &[TakingGame]) -> Option<(usize, usize)> {
+        let heaps = Self::as_nim_heaps(parts)?;
+        if heaps.iter().filter(|&&h| h > 1).count() > 1 {
+            return None;
+        }
+        Some((*heaps.last()?, heaps.len() - 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use evaluator::Evaluator;
+
+    use crate::{builder::Builder, hypergraph::Set, taking_game::TakingGame};
+
+    #[test]
+    fn test_as_nim_heaps() {
+        let heap = Builder::heap(5).build();
+        assert_eq!(TakingGame::as_nim_heaps(&heap), Some(vec![5]));
+        let kayles = Builder::kayles(3).build();
+        assert_eq!(TakingGame::as_nim_heaps(&kayles), None);
+        assert_eq!(TakingGame::as_nim_heaps(&[]), Some(vec![]));
+
+        let two_heaps = Builder::heap(2).sum(Builder::heap(3)).build();
+        assert_eq!(TakingGame::as_nim_heaps(&two_heaps), Some(vec![2, 3]));
+        let mixed = Builder::heap(2).sum(Builder::kayles(3)).build();
+        assert_eq!(TakingGame::as_nim_heaps(&mixed), None);
+
+        let parts = Builder::heap(2)
+            .sum(Builder::heap(3))
+            .sum(Builder::heap(6))
+            .build();
+        let heaps = TakingGame::as_nim_heaps(&parts).unwrap();
+        let eval = Evaluator::new();
+        assert_eq!(
+            eval.get_nimber_by_parts(&parts),
+            Some(heaps.iter().fold(0, |acc, h| acc ^ h))
+        );
+    }
+
+    #[test]
+    fn test_degree_and_edge_size_sequences() {
+        let a = Builder::kayles(5).build_one().unwrap();
+        let b = Builder::from_hyperedges(vec![vec![7, 3], vec![9, 1], vec![3, 9], vec![5, 7]])
+            .build_one()
+            .unwrap();
+        assert_eq!(a.degree_sequence(), vec![1, 1, 2, 2, 2]);
+        assert_eq!(a.degree_sequence(), b.degree_sequence());
+        assert_eq!(a.edge_size_sequence(), vec![2, 2, 2, 2]);
+        assert_eq!(a.edge_size_sequence(), b.edge_size_sequence());
+
+        let triangle = Builder::triangle(3).build_one().unwrap();
+        assert_eq!(triangle.edge_size_sequence(), vec![2, 2, 2, 3, 3, 3]);
+    }
+
+    #[test]
+    fn test_as_generalized_heap() {
+        let eval = Evaluator::new();
+        for (edge_size, lone_count) in [(1, 0), (4, 0), (5, 1), (3, 2), (1, 3), (6, 5)] {
+            let mut b = Builder::heap(edge_size);
+            for _ in 0..lone_count {
+                b = b.sum(Builder::unit());
+            }
+            let parts = b.build();
+            assert_eq!(
+                TakingGame::as_generalized_heap(&parts),
+                Some((edge_size, lone_count))
+            );
+            assert_eq!(
+                eval.get_nimber_by_parts(&parts),
+                Some(edge_size ^ (lone_count % 2))
+            );
+        }
+
+        let two_heaps = Builder::heap(2).sum(Builder::heap(3)).build();
+        assert_eq!(TakingGame::as_generalized_heap(&two_heaps), None);
+        let kayles = Builder::kayles(3).build();
+        assert_eq!(TakingGame::as_generalized_heap(&kayles), None);
+    }
+
+    #[test]
+    fn test_reduce_twin_nodes() {
+        let heap = Builder::heap(4).build_one().unwrap();
+        let (collapsed, multiplicities) = heap.reduce_twin_nodes();
+        assert_eq!(collapsed, Builder::unit().build_one().unwrap());
+        assert_eq!(multiplicities, vec![4]);
+
+        // Nodes 0, 1 and 3, 4 are twins, collapsing to Kayles on three nodes.
+        let g = Builder::from_hyperedges(vec![vec![0, 1, 2], vec![2, 3, 4]])
+            .build_one()
+            .unwrap();
+        let (collapsed, multiplicities) = g.reduce_twin_nodes();
+        assert_eq!(collapsed, Builder::kayles(3).build_one().unwrap());
+        let mut sorted = multiplicities.clone();
+        sorted.sort();
+        assert_eq!(sorted, vec![1, 2, 2]);
+
+        // Expanding every node by its multiplicity restores the game and its nimber.
+        let mut offsets = vec![0];
+        for m in &multiplicities {
+            offsets.push(offsets.last().unwrap() + m);
+        }
+        let expanded: Vec<Vec<usize>> = collapsed
+            .graph
+            .hyperedges()
+            .iter()
+            .map(|e| e.iter().flat_map(|n| offsets[n]..offsets[n + 1]).collect())
+            .collect();
+        let expanded = Builder::from_hyperedges(expanded).build_one().unwrap();
+        assert_eq!(expanded, g);
+        let eval = Evaluator::new();
+        assert_eq!(eval.get_nimber(&expanded), eval.get_nimber(&g));
+
+        let square = Builder::rect(2, 2).build_one().unwrap();
+        let (collapsed, multiplicities) = square.reduce_twin_nodes();
+        assert_eq!(collapsed, square);
+        assert_eq!(multiplicities, vec![1; 4]);
+    }
+}
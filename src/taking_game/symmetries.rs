@@ -10,7 +10,7 @@ impl TakingGame {
     /// Returns `Some(vec)` if a valid mapping is found, where `vec[i]` is the node
     /// symmetric to `i`. Returns `None` if no symmetry exists.
     pub fn find_symmetry(&self) -> Option<Vec<usize>> {
-        if self.graph.nr_nodes().is_multiple_of(2)
+        if self.has_even_token_count()
             && self.graph.hyperedges().len().is_multiple_of(2)
             && self
                 .graph
@@ -31,6 +31,196 @@ impl TakingGame {
         }
     }
 
+    /// Returns true if `self` and `other` are the same game up to relabelling nodes.
+    ///
+    /// Equal canonical forms (see [`PartialEq`]) always imply isomorphism. As the
+    /// canonical ordering is not guaranteed to be unique for highly symmetric games,
+    /// this falls back to [`TakingGame::isomorphism_to`] if the forms differ.
+    pub fn is_isomorphic_to(&self, other: &Self) -> bool {
+        self == other || self.isomorphism_to(other).is_some()
+    }
+
+    /// Searches for a node bijection mapping the hyperedges of `self` onto those of `other`.
+    ///
+    /// Returns `Some(vec)` where the node labelled `self.nodes()[i]` corresponds to the
+    /// node labelled `vec[i]` in `other`, or `None` if the games are not isomorphic.
+    pub fn isomorphism_to(&self, other: &Self) -> Option<Vec<usize>> {
+        let n = self.nr_nodes();
+        if n != other.nr_nodes() || self.graph.hyperedges().len() != other.graph.hyperedges().len()
+        {
+            return None;
+        }
+        let candidates = vec![(0..n).collect(); n];
+        let mut isomorphism = None;
+        self.search_isomorphisms(other, &candidates, &mut |mapping| {
+            isomorphism = Some(mapping.iter().map(|&j| other.nodes()[j]).collect());
+            true
+        });
+        isomorphism
+    }
+
+    /// Returns all nontrivial automorphisms of the game.
+    ///
+    /// Unlike [`TakingGame::find_symmetry`], which only looks for a single involution
+    /// pairing nodes that share no hyperedge (the condition forcing nimber 0), this
+    /// enumerates every structure-preserving bijection, so the full automorphism
+    /// group is the returned list plus the identity. Each entry maps node `i` to
+    /// `vec[i]`. The group can be exponentially large, so this is meant for small games.
+    pub fn find_all_symmetries(&self) -> Vec<Vec<usize>> {
+        let mut symmetries = vec![];
+        self.search_automorphisms(&mut |mapping| {
+            if mapping.iter().enumerate().any(|(i, &j)| i != j) {
+                symmetries.push(mapping.to_vec());
+            }
+            false
+        });
+        symmetries
+    }
+
+    /// Searches for an automorphism all of whose cycles have length `order`.
+    ///
+    /// Such an automorphism splits the nodes into orbits of `order` nodes each, like
+    /// the 4-fold rotation of a square torus. For `order == 2` this is any
+    /// fixed-point-free involution, which unlike [`TakingGame::find_symmetry`] may
+    /// pair nodes sharing a hyperedge. Each entry of the result maps node `i` to `vec[i]`.
+    pub fn find_rotational_symmetry(&self, order: usize) -> Option<Vec<usize>> {
+        if order == 0 || !self.nr_nodes().is_multiple_of(order) {
+            return None;
+        }
+        let mut symmetry = None;
+        self.search_automorphisms(&mut |mapping| {
+            let has_order = (0..mapping.len()).all(|start| {
+                let mut node = start;
+                for step in 1..=order {
+                    node = mapping[node];
+                    if node == start {
+                        return step == order;
+                    }
+                }
+                false
+            });
+            if has_order {
+                symmetry = Some(mapping.to_vec());
+            }
+            has_order
+        });
+        symmetry
+    }
+
+    /// Enumerates automorphisms, passing each to `found` until it returns true.
+    ///
+    /// Automorphisms preserve the structural node partitions, so every node only
+    /// needs to be tried against the nodes of its own partition.
+    fn search_automorphisms(&self, found: &mut dyn FnMut(&[usize]) -> bool) {
+        let mut candidates = vec![vec![]; self.nr_nodes()];
+        for partition in self.graph.get_node_partitions() {
+            for node in partition.clone() {
+                candidates[node] = partition.clone().collect();
+            }
+        }
+        self.search_isomorphisms(self, &candidates, found);
+    }
+
+    /// Enumerates isomorphisms from `self` to `target` by backtracking, passing each
+    /// complete mapping to `found` until it returns true.
+    ///
+    /// Node `i` is only mapped to nodes in `candidates[i]`.
+    fn search_isomorphisms(
+        &self,
+        target: &Self,
+        candidates: &[Vec<usize>],
+        found: &mut dyn FnMut(&[usize]) -> bool,
+    ) {
+        let mut target_edges = target.graph.hyperedges().to_vec();
+        target_edges.sort();
+        self.extend_isomorphism(
+            &mut Vec::with_capacity(self.nr_nodes()),
+            &self.co_occurrences(),
+            &target.co_occurrences(),
+            &target_edges,
+            candidates,
+            found,
+        );
+    }
+
+    /// Recursively extends a partial isomorphism `mapping` by backtracking.
+    ///
+    /// A node may only map to a node lying in as many hyperedges, and each pair of
+    /// mapped nodes must share as many hyperedges in both games. Complete mappings
+    /// are checked against the sorted hyperedges of the target. Returns true once
+    /// `found` asked to stop.
+    fn extend_isomorphism(
+        &self,
+        mapping: &mut Vec<usize>,
+        co_occurrences: &[Vec<usize>],
+        target_co_occurrences: &[Vec<usize>],
+        target_edges: &[Bitset128],
+        candidates: &[Vec<usize>],
+        found: &mut dyn FnMut(&[usize]) -> bool,
+    ) -> bool {
+        let node = mapping.len();
+        if node == co_occurrences.len() {
+            let mut mapped_edges: Vec<Bitset128> = self
+                .graph
+                .hyperedges()
+                .iter()
+                .map(|e| Bitset128::from_slice(&e.iter().map(|n| mapping[n]).collect::<Vec<_>>()))
+                .collect();
+            mapped_edges.sort();
+            return mapped_edges == target_edges && found(mapping);
+        }
+        for &cand in &candidates[node] {
+            if mapping.contains(&cand)
+                || co_occurrences[node][node] != target_co_occurrences[cand][cand]
+                || mapping
+                    .iter()
+                    .enumerate()
+                    .any(|(k, &m)| co_occurrences[node][k] != target_co_occurrences[cand][m])
+            {
+                continue;
+            }
+            mapping.push(cand);
+            if self.extend_isomorphism(
+                mapping,
+                co_occurrences,
+                target_co_occurrences,
+                target_edges,
+                candidates,
+                found,
+            ) {
+                return true;
+            }
+            mapping.pop();
+        }
+        false
+    }
+
+    /// Counts for every pair of nodes the hyperedges containing both.
+    ///
+    /// The diagonal holds the number of hyperedges containing each node.
+    fn co_occurrences(&self) -> Vec<Vec<usize>> {
+        let n = self.nr_nodes();
+        let mut counts = vec![vec![0; n]; n];
+        for edge in self.graph.hyperedges() {
+            for a in edge.iter() {
+                for b in edge.iter() {
+                    counts[a][b] += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    /// Returns true if the game has an even number of tokens (nodes).
+    ///
+    /// This is necessary for a fixed-point-free involution, so a game with an odd
+    /// token count never has a symmetry in the sense of [`TakingGame::find_symmetry`].
+    /// Note that the token parity says nothing about the nimber itself, e.g.
+    /// `kayles(4)` has four tokens and nimber 1.
+    pub fn has_even_token_count(&self) -> bool {
+        self.graph.nr_nodes().is_multiple_of(2)
+    }
+
     /// Recursively pairs nodes into symmetric matches from candidate groups.
     ///
     /// Builds a full involutive mapping (`symmetries[node] = cand` and `symmetries[cand] = node`)
@@ -105,10 +295,10 @@ impl TakingGame {
         let candidate_neighbours = &neighbourhoods[candidate];
 
         for neighbour in neighbourhoods[node].iter() {
-            if let Some(mapped) = symmetries[neighbour] {
-                if !candidate_neighbours.contains(&mapped) {
-                    return false;
-                }
+            if let Some(mapped) = symmetries[neighbour]
+                && !candidate_neighbours.contains(&mapped)
+            {
+                return false;
             }
         }
         true
@@ -132,6 +322,122 @@ impl TakingGame {
 #[cfg(test)]
 mod tests {
     use crate::builder::Builder;
+    use crate::hypergraph::Set;
+    use itertools::Itertools;
+
+    #[test]
+    fn test_token_parity_and_symmetry() {
+        for (name, builder) in Builder::book() {
+            for g in builder.build() {
+                if g.find_symmetry().is_some() {
+                    assert!(g.has_even_token_count(), "{name}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_isomorphism_to() {
+        let g = Builder::rect(3, 4).build_one().unwrap();
+        let relabelled: Vec<Vec<usize>> = g
+            .labeled_hyperedges()
+            .iter()
+            .map(|e| e.iter().map(|n| 100 - 3 * n).collect())
+            .collect();
+        let h = Builder::from_hyperedges(relabelled).build_one().unwrap();
+        assert!(g.is_isomorphic_to(&h));
+
+        let mapping = g.isomorphism_to(&h).unwrap();
+        let mapped: Vec<Vec<usize>> = g
+            .labeled_hyperedges()
+            .iter()
+            .map(|e| {
+                e.iter()
+                    .map(|n| mapping[g.nodes().iter().position(|m| m == n).unwrap()])
+                    .sorted()
+                    .collect()
+            })
+            .sorted()
+            .collect();
+        let expected: Vec<Vec<usize>> = h
+            .labeled_hyperedges()
+            .into_iter()
+            .map(|e| e.into_iter().sorted().collect())
+            .sorted()
+            .collect();
+        assert_eq!(mapped, expected);
+
+        let other = Builder::rect(2, 6).build_one().unwrap();
+        assert!(!g.is_isomorphic_to(&other));
+        assert!(g.isomorphism_to(&other).is_none());
+    }
+
+    #[test]
+    fn test_isomorphism_of_relabelled_cycles() {
+        let a = Builder::cycle(5).build_one().unwrap();
+        let b = Builder::from_hyperedges(vec![
+            vec![0, 2],
+            vec![2, 4],
+            vec![4, 1],
+            vec![1, 3],
+            vec![3, 0],
+        ])
+        .build_one()
+        .unwrap();
+        assert!(a.is_isomorphic_to(&b));
+        assert!(!a.is_isomorphic_to(&Builder::kayles(5).build_one().unwrap()));
+    }
+
+    #[test]
+    fn test_find_all_symmetries() {
+        let square = Builder::rect(2, 2).build_one().unwrap();
+        let symmetries = square.find_all_symmetries();
+        assert_eq!(symmetries.len(), 7);
+        let edges: Vec<Vec<usize>> = square
+            .graph
+            .hyperedges()
+            .iter()
+            .map(|e| e.iter().collect())
+            .sorted()
+            .collect();
+        for mapping in &symmetries {
+            let mapped: Vec<Vec<usize>> = edges
+                .iter()
+                .map(|e| e.iter().map(|&n| mapping[n]).sorted().collect())
+                .sorted()
+                .collect();
+            assert_eq!(mapped, edges);
+        }
+
+        let path = Builder::kayles(4).build_one().unwrap();
+        assert_eq!(path.find_all_symmetries().len(), 1);
+        let star = Builder::star(4).build_one().unwrap();
+        assert_eq!(star.find_all_symmetries().len(), 5);
+    }
+
+    #[test]
+    fn test_find_rotational_symmetry() {
+        let torus = Builder::torus(4, 4).build_one().unwrap();
+        for order in [1, 2, 4] {
+            let rotation = torus.find_rotational_symmetry(order).unwrap();
+            for start in 0..rotation.len() {
+                let mut node = start;
+                for _ in 0..order {
+                    node = rotation[node];
+                }
+                assert_eq!(node, start);
+            }
+        }
+        assert!(torus.find_rotational_symmetry(3).is_none());
+        assert!(torus.find_rotational_symmetry(0).is_none());
+
+        let odd_torus = Builder::torus(3, 3).build_one().unwrap();
+        assert!(odd_torus.find_rotational_symmetry(3).is_some());
+        assert!(odd_torus.find_rotational_symmetry(2).is_none());
+
+        let path = Builder::kayles(4).build_one().unwrap();
+        assert!(path.find_rotational_symmetry(4).is_none());
+    }
 
     #[test]
     fn test_rect_4_8() {
@@ -0,0 +1,115 @@
+use std::fmt::Display;
+
+use super::{TakingGame, to_bitsets};
+use crate::hypergraph::Set;
+
+/// A problem in the input of [`TakingGame::from_hyperedges_checked`].
+///
+/// Edges are identified by their index in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Diagnostic {
+    /// The edge contains no nodes.
+    EmptyEdge(usize),
+    /// The edge contains the same nodes as the earlier edge `first`.
+    DuplicateEdge { edge: usize, first: usize },
+    /// The edge is a proper subset of `superset`, so it adds no moves.
+    SubsetRemoved { edge: usize, superset: usize },
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Diagnostic::EmptyEdge(e) => write!(f, "edge {e} is empty"),
+            Diagnostic::DuplicateEdge { edge, first } => {
+                write!(f, "edge {edge} duplicates edge {first}")
+            }
+            Diagnostic::SubsetRemoved { edge, superset } => {
+                write!(f, "edge {edge} is a subset of edge {superset}")
+            }
+        }
+    }
+}
+
+/// The input of [`TakingGame::from_hyperedges_checked`] contained redundant edges.
+///
+/// The redundant edges are dropped as usual, so `games` holds the same
+/// components an unchecked build would produce.
+#[derive(Debug, Clone)]
+pub struct BuildError {
+    /// Every redundant edge found, in input order.
+    pub diagnostics: Vec<Diagnostic>,
+    /// The components built from the input.
+    pub games: Vec<TakingGame>,
+}
+
+impl Display for BuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let messages: Vec<String> = self.diagnostics.iter().map(|d| d.to_string()).collect();
+        write!(f, "redundant input edges: {}", messages.join(", "))
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+impl TakingGame {
+    /// Builds the components like [`TakingGame::from_hyperesges`], but reports
+    /// empty, duplicate and subset edges instead of silently dropping them.
+    pub fn from_hyperedges_checked(edges: Vec<Vec<usize>>) -> Result<Vec<Self>, BuildError> {
+        let sets = to_bitsets(&edges);
+        let mut diagnostics = vec![];
+        for (edge, set) in sets.iter().enumerate() {
+            if set.is_empty() {
+                diagnostics.push(Diagnostic::EmptyEdge(edge));
+            } else if let Some(first) = sets[..edge].iter().position(|s| s == set) {
+                diagnostics.push(Diagnostic::DuplicateEdge { edge, first });
+            } else if let Some(superset) = sets.iter().position(|s| s != set && set.is_subset(s)) {
+                diagnostics.push(Diagnostic::SubsetRemoved { edge, superset });
+            }
+        }
+        let games = Self::from_hyperesges(edges);
+        if diagnostics.is_empty() {
+            Ok(games)
+        } else {
+            Err(BuildError { diagnostics, games })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::Builder;
+
+    #[test]
+    fn test_subset_removed() {
+        let err = TakingGame::from_hyperedges_checked(vec![vec![0, 1], vec![0, 1, 2]]).unwrap_err();
+        assert_eq!(
+            err.diagnostics,
+            vec![Diagnostic::SubsetRemoved {
+                edge: 0,
+                superset: 1
+            }]
+        );
+        assert_eq!(err.games, Builder::heap(3).build());
+    }
+
+    #[test]
+    fn test_empty_and_duplicate_edges() {
+        let err =
+            TakingGame::from_hyperedges_checked(vec![vec![1, 0], vec![], vec![0, 1]]).unwrap_err();
+        assert_eq!(
+            err.diagnostics,
+            vec![
+                Diagnostic::EmptyEdge(1),
+                Diagnostic::DuplicateEdge { edge: 2, first: 0 }
+            ]
+        );
+        assert_eq!(
+            err.to_string(),
+            "redundant input edges: edge 1 is empty, edge 2 duplicates edge 0"
+        );
+
+        let games = TakingGame::from_hyperedges_checked(vec![vec![0, 1], vec![1, 2]]).unwrap();
+        assert_eq!(games, Builder::kayles(3).build());
+    }
+}